@@ -0,0 +1,192 @@
+use crate::client_socket::entity::frame_format::{ByteOrder, FrameFormat, LengthFieldSize};
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+// Turns payloads into length-prefixed frames and reassembles frames from arbitrary read chunks
+#[derive(Debug)]
+pub struct LengthPrefixedCodec {
+    frame_format: FrameFormat,
+    read_buffer: Vec<u8>,
+}
+
+impl LengthPrefixedCodec {
+    pub fn new(frame_format: FrameFormat) -> Self {
+        LengthPrefixedCodec {
+            frame_format,
+            read_buffer: Vec::new(),
+        }
+    }
+
+    pub fn get_frame_format(&self) -> &FrameFormat {
+        &self.frame_format
+    }
+
+    pub fn buffered_len(&self) -> usize {
+        self.read_buffer.len()
+    }
+
+    pub fn encode(&self, payload: &[u8]) -> Result<Vec<u8>, ClientSocketError> {
+        self.check_frame_size(payload.len() as u64)?;
+
+        let length_field_size = self.frame_format.get_length_field_size();
+        let mut frame = Vec::with_capacity(length_field_size.byte_count() + payload.len());
+        frame.extend_from_slice(&self.encode_length(payload.len() as u64));
+        frame.extend_from_slice(payload);
+
+        Ok(frame)
+    }
+
+    // Buffers the chunk and returns every frame it completes; an incomplete tail waits for the next chunk.
+    // A bad header after complete frames is reported by the next call, so those frames are not lost.
+    pub fn decode(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>, ClientSocketError> {
+        self.read_buffer.extend_from_slice(chunk);
+
+        let header_size = self.frame_format.get_length_field_size().byte_count();
+        let mut frame_list = Vec::new();
+        let mut offset = 0;
+
+        while self.read_buffer.len() - offset >= header_size {
+            let length = self.decode_length(&self.read_buffer[offset..offset + header_size]);
+            let frame_end = match self.find_frame_end(offset + header_size, length) {
+                Ok(frame_end) => frame_end,
+                Err(_) if !frame_list.is_empty() => break,
+                Err(error) => return Err(error),
+            };
+
+            if self.read_buffer.len() < frame_end {
+                break;
+            }
+
+            frame_list.push(self.read_buffer[offset + header_size..frame_end].to_vec());
+            offset = frame_end;
+        }
+
+        self.read_buffer.drain(..offset);
+        Ok(frame_list)
+    }
+
+    fn find_frame_end(&self, payload_start: usize, length: u64) -> Result<usize, ClientSocketError> {
+        self.check_frame_size(length)?;
+
+        usize::try_from(length)
+            .ok()
+            .and_then(|length| payload_start.checked_add(length))
+            .ok_or(ClientSocketError::FrameTooLarge { size: length, max_size: (usize::MAX - payload_start) as u64 })
+    }
+
+    fn check_frame_size(&self, length: u64) -> Result<(), ClientSocketError> {
+        let max_frame_size = self.frame_format.get_max_payload_size();
+
+        if length > max_frame_size {
+            return Err(ClientSocketError::FrameTooLarge { size: length, max_size: max_frame_size });
+        }
+
+        Ok(())
+    }
+
+    fn encode_length(&self, length: u64) -> Vec<u8> {
+        match (self.frame_format.get_length_field_size(), self.frame_format.get_byte_order()) {
+            (LengthFieldSize::Two, ByteOrder::BigEndian) => (length as u16).to_be_bytes().to_vec(),
+            (LengthFieldSize::Two, ByteOrder::LittleEndian) => (length as u16).to_le_bytes().to_vec(),
+            (LengthFieldSize::Four, ByteOrder::BigEndian) => (length as u32).to_be_bytes().to_vec(),
+            (LengthFieldSize::Four, ByteOrder::LittleEndian) => (length as u32).to_le_bytes().to_vec(),
+            (LengthFieldSize::Eight, ByteOrder::BigEndian) => length.to_be_bytes().to_vec(),
+            (LengthFieldSize::Eight, ByteOrder::LittleEndian) => length.to_le_bytes().to_vec(),
+        }
+    }
+
+    fn decode_length(&self, header: &[u8]) -> u64 {
+        // Left-pad (big endian) or right-pad (little endian) to eight bytes so every size decodes the same way
+        let mut bytes = [0u8; 8];
+
+        match self.frame_format.get_byte_order() {
+            ByteOrder::BigEndian => {
+                bytes[8 - header.len()..].copy_from_slice(header);
+                u64::from_be_bytes(bytes)
+            }
+            ByteOrder::LittleEndian => {
+                bytes[..header.len()].copy_from_slice(header);
+                u64::from_le_bytes(bytes)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_header_layouts() {
+        let big_endian_two = LengthPrefixedCodec::new(FrameFormat::new(LengthFieldSize::Two, ByteOrder::BigEndian));
+        assert_eq!(big_endian_two.encode(b"abc").unwrap(), vec![0, 3, b'a', b'b', b'c']);
+
+        let little_endian_four = LengthPrefixedCodec::new(FrameFormat::new(LengthFieldSize::Four, ByteOrder::LittleEndian));
+        assert_eq!(little_endian_four.encode(b"a").unwrap(), vec![1, 0, 0, 0, b'a']);
+
+        let big_endian_eight = LengthPrefixedCodec::new(FrameFormat::new(LengthFieldSize::Eight, ByteOrder::BigEndian));
+        assert_eq!(big_endian_eight.encode(b"").unwrap(), vec![0; 8]);
+    }
+
+    #[test]
+    fn test_round_trip_every_layout() {
+        for length_field_size in [LengthFieldSize::Two, LengthFieldSize::Four, LengthFieldSize::Eight] {
+            for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+                let mut codec = LengthPrefixedCodec::new(FrameFormat::new(length_field_size, byte_order));
+                let frame = codec.encode(b"payload").unwrap();
+
+                assert_eq!(codec.decode(&frame).unwrap(), vec![b"payload".to_vec()]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_partial_and_coalesced_chunks() {
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default());
+
+        let mut stream = codec.encode(b"first").unwrap();
+        stream.extend(codec.encode(b"second").unwrap());
+
+        // Header split across reads
+        assert!(codec.decode(&stream[..2]).unwrap().is_empty());
+        assert_eq!(codec.buffered_len(), 2);
+
+        // Completes the first frame and starts the second
+        assert_eq!(codec.decode(&stream[2..12]).unwrap(), vec![b"first".to_vec()]);
+        assert_eq!(codec.decode(&stream[12..]).unwrap(), vec![b"second".to_vec()]);
+        assert_eq!(codec.buffered_len(), 0);
+    }
+
+    #[test]
+    fn test_max_frame_size() {
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default().with_max_frame_size(4));
+
+        assert_eq!(
+            codec.encode(b"too long").unwrap_err(),
+            ClientSocketError::FrameTooLarge { size: 8, max_size: 4 }
+        );
+        assert_eq!(
+            codec.decode(&[0, 0, 0, 5]).unwrap_err(),
+            ClientSocketError::FrameTooLarge { size: 5, max_size: 4 }
+        );
+
+        // A two-byte header cannot describe more than u16::MAX bytes whatever the configured maximum
+        let two_byte_codec = LengthPrefixedCodec::new(FrameFormat::new(LengthFieldSize::Two, ByteOrder::BigEndian));
+        assert!(two_byte_codec.encode(&vec![0u8; u16::MAX as usize + 1]).is_err());
+    }
+
+    #[test]
+    fn test_frames_before_bad_header_are_kept() {
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default().with_max_frame_size(4));
+
+        assert_eq!(codec.decode(&[0, 0, 0, 2, b'o', b'k', 0, 0, 0, 5]).unwrap(), vec![b"ok".to_vec()]);
+        assert_eq!(codec.decode(&[]).unwrap_err(), ClientSocketError::FrameTooLarge { size: 5, max_size: 4 });
+    }
+
+    #[test]
+    fn test_length_past_address_space() {
+        let frame_format = FrameFormat::new(LengthFieldSize::Eight, ByteOrder::BigEndian).with_max_frame_size(usize::MAX);
+        let mut codec = LengthPrefixedCodec::new(frame_format);
+
+        assert!(matches!(codec.decode(&[0xff; 8]), Err(ClientSocketError::FrameTooLarge { .. })));
+    }
+}
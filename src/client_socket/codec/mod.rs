@@ -0,0 +1,2 @@
+pub mod length_prefixed_codec;
+pub mod sequence_number_codec;
\ No newline at end of file
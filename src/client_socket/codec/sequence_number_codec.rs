@@ -0,0 +1,38 @@
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub const SEQUENCE_NUMBER_SIZE: usize = 8;
+
+// Prefixes datagrams with a u64 big endian sequence number
+pub struct SequenceNumberCodec;
+
+impl SequenceNumberCodec {
+    pub fn encode(sequence_number: u64, payload: &[u8]) -> Vec<u8> {
+        let mut datagram = Vec::with_capacity(SEQUENCE_NUMBER_SIZE + payload.len());
+        datagram.extend_from_slice(&sequence_number.to_be_bytes());
+        datagram.extend_from_slice(payload);
+        datagram
+    }
+
+    pub fn decode(datagram: &[u8]) -> Result<(u64, &[u8]), ClientSocketError> {
+        let Some((header, payload)) = datagram.split_first_chunk::<SEQUENCE_NUMBER_SIZE>() else {
+            return Err(ClientSocketError::InvalidMessage(format!("datagram of {} bytes has no sequence number", datagram.len())));
+        };
+
+        Ok((u64::from_be_bytes(*header), payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode() {
+        let datagram = SequenceNumberCodec::encode(258, b"cpu=42");
+
+        assert_eq!(&datagram[..SEQUENCE_NUMBER_SIZE], &[0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(SequenceNumberCodec::decode(&datagram).unwrap(), (258, &b"cpu=42"[..]));
+        assert_eq!(SequenceNumberCodec::decode(&datagram[..SEQUENCE_NUMBER_SIZE]).unwrap(), (258, &b""[..]));
+        assert!(matches!(SequenceNumberCodec::decode(&datagram[..3]), Err(ClientSocketError::InvalidMessage(_))));
+    }
+}
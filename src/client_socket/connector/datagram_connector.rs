@@ -0,0 +1,19 @@
+use tokio::net::UdpSocket;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub struct DatagramConnector;
+
+impl DatagramConnector {
+    // Connecting a UDP socket only fixes the peer: send() needs no address and recv() ignores other senders
+    pub async fn connect(config: &DatagramSocketConfig) -> Result<UdpSocket, ClientSocketError> {
+        let socket = UdpSocket::bind(config.get_bind_address()).await
+            .map_err(|error| ClientSocketError::ConnectFailed { address: config.get_bind_address().to_string(), message: error.to_string() })?;
+
+        let address = config.address();
+        socket.connect(&address).await
+            .map_err(|error| ClientSocketError::ConnectFailed { address, message: error.to_string() })?;
+
+        Ok(socket)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod datagram_connector;
+pub mod socket_connector;
+#[cfg(feature = "tls")]
+pub mod tls_connector;
+pub mod websocket_connector;
\ No newline at end of file
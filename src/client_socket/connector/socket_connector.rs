@@ -0,0 +1,51 @@
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+#[cfg(feature = "tls")]
+use crate::client_socket::connector::tls_connector::TlsConnector;
+use crate::client_socket::connector::websocket_connector::WebSocketConnector;
+use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+use crate::client_socket::entity::socket_connection::SocketConnection;
+use crate::client_socket::entity::socket_stream::BoxedSocketStream;
+use crate::client_socket::entity::transport::Transport;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub struct SocketConnector;
+
+impl SocketConnector {
+    pub async fn connect(config: &ClientSocketConfig) -> Result<SocketConnection, ClientSocketError> {
+        let address = config.address();
+
+        // The timeout covers the TLS and WebSocket handshakes as well as the TCP connect
+        match timeout(config.get_connect_timeout(), SocketConnector::open(config, &address)).await {
+            Ok(result) => result,
+            Err(_) => Err(ClientSocketError::ConnectTimeout(address)),
+        }
+    }
+
+    async fn open(config: &ClientSocketConfig, address: &str) -> Result<SocketConnection, ClientSocketError> {
+        let stream = TcpStream::connect(address).await
+            .map_err(|error| ClientSocketError::ConnectFailed { address: address.to_string(), message: error.to_string() })?;
+        let stream = SocketConnector::secure(config, stream).await?;
+
+        match config.get_transport() {
+            Transport::Tcp => Ok(SocketConnection::Stream(stream)),
+            Transport::WebSocket(websocket_config) => {
+                let websocket_stream = WebSocketConnector::handshake(stream, config, websocket_config).await?;
+                Ok(SocketConnection::WebSocket(Box::new(websocket_stream)))
+            }
+        }
+    }
+
+    #[cfg(feature = "tls")]
+    async fn secure(config: &ClientSocketConfig, stream: TcpStream) -> Result<BoxedSocketStream, ClientSocketError> {
+        match config.get_tls_config() {
+            Some(tls_config) => Ok(Box::new(TlsConnector::connect(stream, config.get_host(), tls_config).await?)),
+            None => Ok(Box::new(stream)),
+        }
+    }
+
+    #[cfg(not(feature = "tls"))]
+    async fn secure(_config: &ClientSocketConfig, stream: TcpStream) -> Result<BoxedSocketStream, ClientSocketError> {
+        Ok(Box::new(stream))
+    }
+}
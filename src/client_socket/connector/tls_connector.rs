@@ -0,0 +1,288 @@
+use std::sync::Arc;
+use sha2::{Digest, Sha256};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, Error as RustlsError, RootCertStore, SignatureScheme};
+use crate::client_socket::entity::tls_config::TlsConfig;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub struct TlsConnector;
+
+impl TlsConnector {
+    pub async fn connect(stream: TcpStream, host: &str, tls_config: &TlsConfig) -> Result<TlsStream<TcpStream>, ClientSocketError> {
+        let server_name = tls_config.get_server_name().unwrap_or(host);
+        let server_name = ServerName::try_from(server_name.to_string())
+            .map_err(|error| ClientSocketError::TlsConfig(format!("invalid server name {}: {}", server_name, error)))?;
+
+        let connector = tokio_rustls::TlsConnector::from(TlsConnector::build_client_config(tls_config)?);
+        connector.connect(server_name, stream).await
+            .map_err(|error| ClientSocketError::TlsHandshake { address: host.to_string(), message: error.to_string() })
+    }
+
+    pub fn build_client_config(tls_config: &TlsConfig) -> Result<Arc<ClientConfig>, ClientSocketError> {
+        let provider = Arc::new(ring::default_provider());
+        let root_store = load_root_store(tls_config)?;
+
+        if root_store.is_empty() && tls_config.get_pinned_certificate_sha256_list().is_empty() {
+            return Err(ClientSocketError::TlsConfig("no CA certificate or certificate pin to trust the server with".to_string()));
+        }
+
+        let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(tls_config_error)?;
+
+        let builder = if tls_config.get_pinned_certificate_sha256_list().is_empty() {
+            builder.with_root_certificates(root_store)
+        } else {
+            let ca_verifier = if root_store.is_empty() {
+                None
+            } else {
+                Some(WebPkiServerVerifier::builder_with_provider(Arc::new(root_store), Arc::clone(&provider)).build().map_err(tls_config_error)?)
+            };
+
+            builder.dangerous().with_custom_certificate_verifier(Arc::new(PinnedCertificateVerifier {
+                ca_verifier,
+                pinned_certificate_sha256_list: tls_config.get_pinned_certificate_sha256_list().to_vec(),
+                provider,
+            }))
+        };
+
+        let client_config = match tls_config.get_client_certificate() {
+            Some(client_certificate) => {
+                let certificate_chain = load_certificates(client_certificate.get_certificate_chain_pem())?;
+                let private_key = PrivateKeyDer::from_pem_slice(client_certificate.get_private_key_pem()).map_err(tls_config_error)?;
+                builder.with_client_auth_cert(certificate_chain, private_key).map_err(tls_config_error)?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Arc::new(client_config))
+    }
+
+    pub fn certificate_sha256(certificate: &[u8]) -> [u8; 32] {
+        Sha256::digest(certificate).into()
+    }
+}
+
+fn load_root_store(tls_config: &TlsConfig) -> Result<RootCertStore, ClientSocketError> {
+    let mut root_store = RootCertStore::empty();
+
+    for ca_certificate_pem in tls_config.get_ca_certificate_pem_list() {
+        for ca_certificate in load_certificates(ca_certificate_pem)? {
+            root_store.add(ca_certificate).map_err(tls_config_error)?;
+        }
+    }
+
+    Ok(root_store)
+}
+
+fn load_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, ClientSocketError> {
+    let certificate_list = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(tls_config_error)?;
+
+    if certificate_list.is_empty() {
+        return Err(ClientSocketError::TlsConfig("PEM data contains no certificate".to_string()));
+    }
+
+    Ok(certificate_list)
+}
+
+fn tls_config_error(error: impl std::fmt::Display) -> ClientSocketError {
+    ClientSocketError::TlsConfig(error.to_string())
+}
+
+// Requires the server certificate to match a pin, then (when CA bundles are configured) the usual chain and name checks
+#[derive(Debug)]
+struct PinnedCertificateVerifier {
+    ca_verifier: Option<Arc<WebPkiServerVerifier>>,
+    pinned_certificate_sha256_list: Vec<[u8; 32]>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, RustlsError> {
+        let certificate_sha256 = TlsConnector::certificate_sha256(end_entity);
+        if !self.pinned_certificate_sha256_list.contains(&certificate_sha256) {
+            return Err(RustlsError::General("server certificate does not match any pinned fingerprint".to_string()));
+        }
+
+        match &self.ca_verifier {
+            Some(ca_verifier) => ca_verifier.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now),
+            None => Ok(ServerCertVerified::assertion()),
+        }
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], certificate: &CertificateDer<'_>, signature: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, RustlsError> {
+        verify_tls12_signature(message, certificate, signature, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], certificate: &CertificateDer<'_>, signature: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, RustlsError> {
+        verify_tls13_signature(message, certificate, signature, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio_rustls::rustls::server::WebPkiClientVerifier;
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::TlsAcceptor;
+    use crate::client_socket::connector::socket_connector::SocketConnector;
+    use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+    use crate::client_socket::entity::socket_connection::SocketConnection;
+
+    struct TestPki {
+        ca_pem: String,
+        server_der: CertificateDer<'static>,
+        server_key_pem: String,
+        client_pem: String,
+        client_key_pem: String,
+    }
+
+    fn create_test_pki() -> TestPki {
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let server_certificate = CertificateParams::new(vec!["localhost".to_string()]).unwrap().signed_by(&server_key, &ca).unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let client_certificate = CertificateParams::new(vec!["client".to_string()]).unwrap().signed_by(&client_key, &ca).unwrap();
+
+        TestPki {
+            ca_pem: ca.pem(),
+            server_der: server_certificate.der().clone(),
+            server_key_pem: server_key.serialize_pem(),
+            client_pem: client_certificate.pem(),
+            client_key_pem: client_key.serialize_pem(),
+        }
+    }
+
+    // Echoes the first chunk it reads back over TLS; with `require_client_certificate` the server does mutual TLS
+    async fn start_echo_server(test_pki: &TestPki, require_client_certificate: bool) -> u16 {
+        let provider = Arc::new(ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(Arc::clone(&provider)).with_safe_default_protocol_versions().unwrap();
+
+        let builder = if require_client_certificate {
+            let mut client_roots = RootCertStore::empty();
+            client_roots.add(CertificateDer::from_pem_slice(test_pki.ca_pem.as_bytes()).unwrap()).unwrap();
+            builder.with_client_cert_verifier(WebPkiClientVerifier::builder_with_provider(Arc::new(client_roots), provider).build().unwrap())
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        let server_config = builder.with_single_cert(
+            vec![test_pki.server_der.clone()],
+            PrivateKeyDer::from_pem_slice(test_pki.server_key_pem.as_bytes()).unwrap(),
+        ).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    if let Ok(mut tls_stream) = acceptor.accept(stream).await {
+                        let mut buffer = [0u8; 64];
+                        if let Ok(length) = tls_stream.read(&mut buffer).await {
+                            let _ = tls_stream.write_all(&buffer[..length]).await;
+                            let _ = tls_stream.shutdown().await;
+                        }
+                    }
+                });
+            }
+        });
+
+        port
+    }
+
+    async fn echo(port: u16, tls_config: TlsConfig) -> Result<Vec<u8>, ClientSocketError> {
+        let config = ClientSocketConfig::new("127.0.0.1", port).with_tls(tls_config);
+        let SocketConnection::Stream(mut stream) = SocketConnector::connect(&config).await? else {
+            return Err(ClientSocketError::UnsupportedTransport("echo test needs the TCP transport".to_string()));
+        };
+
+        stream.write_all(b"secret").await.map_err(|error| ClientSocketError::Io(error.to_string()))?;
+        let mut echoed = Vec::new();
+        stream.read_to_end(&mut echoed).await.map_err(|error| ClientSocketError::Io(error.to_string()))?;
+        Ok(echoed)
+    }
+
+    #[tokio::test]
+    async fn test_custom_ca_with_server_name_override() {
+        let test_pki = create_test_pki();
+        let port = start_echo_server(&test_pki, false).await;
+
+        let tls_config = TlsConfig::new().with_ca_certificate_pem(test_pki.ca_pem.as_bytes());
+
+        // The certificate is issued for localhost, not for the address we dial
+        assert!(matches!(echo(port, tls_config.clone()).await, Err(ClientSocketError::TlsHandshake { .. })));
+        assert_eq!(echo(port, tls_config.with_server_name("localhost")).await.unwrap(), b"secret".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls() {
+        let test_pki = create_test_pki();
+        let port = start_echo_server(&test_pki, true).await;
+
+        let tls_config = TlsConfig::new()
+            .with_ca_certificate_pem(test_pki.ca_pem.as_bytes())
+            .with_server_name("localhost");
+
+        // Under TLS 1.3 the server rejects a missing client certificate after the client finished its side
+        assert!(echo(port, tls_config.clone()).await.is_err());
+
+        let tls_config = tls_config.with_client_certificate_pem(test_pki.client_pem.as_bytes(), test_pki.client_key_pem.as_bytes());
+        assert_eq!(echo(port, tls_config).await.unwrap(), b"secret".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_certificate_pinning() {
+        let test_pki = create_test_pki();
+        let port = start_echo_server(&test_pki, false).await;
+        let server_sha256 = TlsConnector::certificate_sha256(&test_pki.server_der);
+
+        // A pin alone is enough to trust a server whose CA we do not have
+        let pinned = TlsConfig::new().with_pinned_certificate_sha256(server_sha256).with_server_name("localhost");
+        assert_eq!(echo(port, pinned).await.unwrap(), b"secret".to_vec());
+
+        let wrong_pin = TlsConfig::new()
+            .with_ca_certificate_pem(test_pki.ca_pem.as_bytes())
+            .with_pinned_certificate_sha256([0u8; 32])
+            .with_server_name("localhost");
+        assert!(matches!(echo(port, wrong_pin).await, Err(ClientSocketError::TlsHandshake { .. })));
+    }
+
+    #[test]
+    fn test_invalid_tls_config() {
+        assert!(matches!(TlsConnector::build_client_config(&TlsConfig::new()), Err(ClientSocketError::TlsConfig(_))));
+        assert!(matches!(
+            TlsConnector::build_client_config(&TlsConfig::new().with_ca_certificate_pem(b"not a certificate")),
+            Err(ClientSocketError::TlsConfig(_))
+        ));
+        assert!(TlsConfig::new().with_ca_certificate_file("/nonexistent/ca.pem").is_err());
+    }
+}
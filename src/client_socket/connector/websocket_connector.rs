@@ -0,0 +1,87 @@
+use tokio_tungstenite::client_async_with_config;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig as ProtocolConfig;
+use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+use crate::client_socket::entity::socket_connection::ClientWebSocketStream;
+use crate::client_socket::entity::socket_stream::BoxedSocketStream;
+use crate::client_socket::entity::websocket_config::WebSocketConfig;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub struct WebSocketConnector;
+
+impl WebSocketConnector {
+    // Runs the HTTP upgrade over an already connected (and, with TLS, already encrypted) stream
+    pub async fn handshake(
+        stream: BoxedSocketStream,
+        config: &ClientSocketConfig,
+        websocket_config: &WebSocketConfig,
+    ) -> Result<ClientWebSocketStream, ClientSocketError> {
+        let scheme = if config.uses_tls() { "wss" } else { "ws" };
+        let path = websocket_config.get_path().trim_start_matches('/');
+        let url = format!("{}://{}/{}", scheme, config.address(), path);
+
+        let max_frame_size = config.get_frame_format().get_max_frame_size();
+        let protocol_config = ProtocolConfig::default()
+            .max_message_size(Some(max_frame_size))
+            .max_frame_size(Some(max_frame_size));
+
+        let (websocket_stream, _response) = client_async_with_config(url.as_str(), stream, Some(protocol_config))
+            .await
+            .map_err(|error| ClientSocketError::WebSocket(format!("handshake with {} failed: {}", url, error)))?;
+
+        Ok(websocket_stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::Message;
+    use crate::client_socket::connector::socket_connector::SocketConnector;
+    use crate::client_socket::entity::socket_connection::SocketConnection;
+    use crate::client_socket::entity::transport::Transport;
+
+    // The callback signature is fixed by tungstenite
+    #[allow(clippy::result_large_err)]
+    #[tokio::test]
+    async fn test_handshake_uses_configured_path() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut requested_path = String::new();
+            let mut websocket_stream = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+                requested_path = request.uri().path().to_string();
+                Ok(response)
+            }).await.unwrap();
+
+            websocket_stream.send(Message::text("welcome")).await.unwrap();
+            requested_path
+        });
+
+        let config = ClientSocketConfig::new("127.0.0.1", port).with_transport(Transport::WebSocket(WebSocketConfig::new("/feed")));
+        let SocketConnection::WebSocket(mut websocket_stream) = SocketConnector::connect(&config).await.unwrap() else {
+            panic!("WebSocket transport should produce a WebSocket connection");
+        };
+
+        assert_eq!(websocket_stream.next().await.unwrap().unwrap(), Message::text("welcome"));
+        assert_eq!(server.await.unwrap(), "/feed");
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejected_by_plain_tcp_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // Accepts and immediately hangs up instead of upgrading
+        tokio::spawn(async move {
+            let _ = listener.accept().await.unwrap();
+        });
+
+        let config = ClientSocketConfig::new("127.0.0.1", port).with_transport(Transport::WebSocket(WebSocketConfig::default()));
+        assert!(matches!(SocketConnector::connect(&config).await, Err(ClientSocketError::WebSocket(_))));
+    }
+}
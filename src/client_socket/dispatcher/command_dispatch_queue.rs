@@ -0,0 +1,85 @@
+use tokio::sync::mpsc;
+use crate::client_socket::dispatcher::command_dispatcher::CommandDispatcher;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+
+// Feeds the messages a receiver claims to one task that runs their handlers one at a time, in wire order.
+// The queue is bounded, so a flood of frames holds up the receiver instead of piling up tasks.
+// Dropping the queue lets the task finish the messages already queued and then stop.
+pub struct CommandDispatchQueue {
+    command_dispatcher: CommandDispatcher,
+    message_sender: mpsc::Sender<ProtocolMessage>,
+}
+
+impl CommandDispatchQueue {
+    pub fn start(command_dispatcher: CommandDispatcher, capacity: usize) -> Self {
+        let (message_sender, mut message_receiver) = mpsc::channel::<ProtocolMessage>(capacity.max(1));
+        let handler_dispatcher = command_dispatcher.clone();
+
+        tokio::spawn(async move {
+            while let Some(message) = message_receiver.recv().await {
+                if let Err(error) = handler_dispatcher.dispatch(message).await {
+                    eprintln!("Dropping incoming message: {}", error);
+                }
+            }
+        });
+
+        CommandDispatchQueue { command_dispatcher, message_sender }
+    }
+
+    // Gives the frame back when no handler claims it; waits for room when the queue is full
+    pub async fn dispatch_frame(&self, frame: Vec<u8>) -> Option<Vec<u8>> {
+        let message = match self.command_dispatcher.claim_frame(frame) {
+            Ok(message) => message,
+            Err(frame) => return Some(frame),
+        };
+
+        if let Err(error) = self.message_sender.send(message).await {
+            eprintln!("Dropping incoming message {}: the dispatch task has stopped", error.0.get_command_code());
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use crate::client_socket::dispatcher::command_dispatcher::{CommandHandler, CommandHandlerFuture};
+    use crate::client_socket::entity::message_kind::MessageKind;
+
+    #[tokio::test]
+    async fn test_dispatch_frame_in_order() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let command_dispatcher = CommandDispatcher::new();
+
+        // Earlier messages take longer, so handlers running side by side would finish in reverse
+        let handler: CommandHandler = Arc::new(move |message: ProtocolMessage| -> CommandHandlerFuture {
+            let sender = sender.clone();
+            Box::pin(async move {
+                let delay = 30 - u64::from(message.get_payload()[0]) * 10;
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                sender.send(message.get_payload()[0]).unwrap();
+            })
+        });
+        command_dispatcher.register_handler(3, handler);
+
+        let dispatch_queue = CommandDispatchQueue::start(command_dispatcher, 1);
+
+        for index in 0..3 {
+            assert_eq!(dispatch_queue.dispatch_frame(ProtocolMessage::event(3, vec![index]).to_bytes()).await, None);
+        }
+
+        // Commands nobody handles and responses belong to whoever reads the receiver's channel
+        let unhandled_frame = ProtocolMessage::event(4, Vec::new()).to_bytes();
+        assert_eq!(dispatch_queue.dispatch_frame(unhandled_frame.clone()).await, Some(unhandled_frame));
+        let response_frame = ProtocolMessage::new(MessageKind::Response, 3, 1, Vec::new()).to_bytes();
+        assert_eq!(dispatch_queue.dispatch_frame(response_frame.clone()).await, Some(response_frame));
+
+        drop(dispatch_queue);
+        for index in 0..3 {
+            assert_eq!(receiver.recv().await, Some(index));
+        }
+    }
+}
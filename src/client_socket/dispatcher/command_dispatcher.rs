@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, PoisonError, RwLock};
+use crate::client_socket::entity::message_kind::MessageKind;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub type CommandHandlerFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+pub type CommandHandler = Arc<dyn Fn(ProtocolMessage) -> CommandHandlerFuture + Send + Sync + 'static>;
+
+#[derive(Default)]
+struct CommandHandlerTable {
+    handler_list: HashMap<u16, CommandHandler>,
+    fallback_handler: Option<CommandHandler>,
+}
+
+// Clones share one handler table, so handlers registered later are seen by a running receiver worker
+#[derive(Clone, Default)]
+pub struct CommandDispatcher {
+    handler_table: Arc<RwLock<CommandHandlerTable>>,
+}
+
+impl CommandDispatcher {
+    pub fn new() -> Self {
+        CommandDispatcher::default()
+    }
+
+    pub fn register_handler(&self, command_code: u16, handler: CommandHandler) {
+        self.handler_table.write().unwrap_or_else(PoisonError::into_inner).handler_list.insert(command_code, handler);
+    }
+
+    pub fn remove_handler(&self, command_code: u16) -> bool {
+        self.handler_table.write().unwrap_or_else(PoisonError::into_inner).handler_list.remove(&command_code).is_some()
+    }
+
+    // Receives every message whose command code has no handler of its own. Those messages are then
+    // claimed by the dispatcher, so the request client's unsolicited receiver no longer sees any of them.
+    pub fn set_fallback_handler(&self, handler: CommandHandler) {
+        self.handler_table.write().unwrap_or_else(PoisonError::into_inner).fallback_handler = Some(handler);
+    }
+
+    pub fn has_handler(&self, command_code: u16) -> bool {
+        self.handler_table.read().unwrap_or_else(PoisonError::into_inner).handler_list.contains_key(&command_code)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let handler_table = self.handler_table.read().unwrap_or_else(PoisonError::into_inner);
+        handler_table.handler_list.is_empty() && handler_table.fallback_handler.is_none()
+    }
+
+    pub async fn dispatch(&self, message: ProtocolMessage) -> Result<(), ClientSocketError> {
+        // Clone the handler out so the table is not locked while it runs
+        let handler = {
+            let handler_table = self.handler_table.read().unwrap_or_else(PoisonError::into_inner);
+            handler_table.handler_list
+                .get(&message.get_command_code())
+                .or(handler_table.fallback_handler.as_ref())
+                .cloned()
+        };
+
+        match handler {
+            Some(handler) => {
+                handler(message).await;
+                Ok(())
+            }
+            None => Err(ClientSocketError::UnknownCommand(message.get_command_code())),
+        }
+    }
+
+    // Gives the frame back when it is not ours to handle: a response, not a protocol message,
+    // or a command with neither a handler of its own nor a fallback
+    pub fn claim_frame(&self, frame: Vec<u8>) -> Result<ProtocolMessage, Vec<u8>> {
+        let message = match ProtocolMessage::from_bytes(&frame) {
+            Ok(message) if message.get_message_kind() != MessageKind::Response => message,
+            _ => return Err(frame),
+        };
+
+        let handler_table = self.handler_table.read().unwrap_or_else(PoisonError::into_inner);
+        if handler_table.handler_list.contains_key(&message.get_command_code()) || handler_table.fallback_handler.is_some() {
+            Ok(message)
+        } else {
+            Err(frame)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn forwarding_handler(sender: mpsc::UnboundedSender<(&'static str, ProtocolMessage)>, label: &'static str) -> CommandHandler {
+        Arc::new(move |message: ProtocolMessage| -> CommandHandlerFuture {
+            let sender = sender.clone();
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                sender.send((label, message)).unwrap();
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_by_command_code_and_fallback() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let command_dispatcher = CommandDispatcher::new();
+
+        command_dispatcher.register_handler(1, forwarding_handler(sender.clone(), "login"));
+        assert_eq!(
+            command_dispatcher.dispatch(ProtocolMessage::event(2, Vec::new())).await.unwrap_err(),
+            ClientSocketError::UnknownCommand(2)
+        );
+
+        command_dispatcher.set_fallback_handler(forwarding_handler(sender, "fallback"));
+
+        command_dispatcher.dispatch(ProtocolMessage::event(1, b"user".to_vec())).await.unwrap();
+        command_dispatcher.dispatch(ProtocolMessage::event(2, Vec::new())).await.unwrap();
+
+        let (label, message) = receiver.recv().await.unwrap();
+        assert_eq!((label, message.get_payload()), ("login", &b"user"[..]));
+        assert_eq!(receiver.recv().await.unwrap().0, "fallback");
+
+        assert!(command_dispatcher.remove_handler(1));
+        assert!(!command_dispatcher.has_handler(1));
+    }
+
+    #[test]
+    fn test_claim_frame() {
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let command_dispatcher = CommandDispatcher::new();
+
+        // Without a handler for it a frame passes straight through
+        let event_frame = ProtocolMessage::event(3, b"tick".to_vec()).to_bytes();
+        assert_eq!(command_dispatcher.claim_frame(event_frame.clone()), Err(event_frame.clone()));
+
+        command_dispatcher.register_handler(3, forwarding_handler(sender.clone(), "tick"));
+        assert_eq!(command_dispatcher.claim_frame(event_frame).unwrap().get_payload(), b"tick");
+
+        let other_frame = ProtocolMessage::event(4, Vec::new()).to_bytes();
+        assert_eq!(command_dispatcher.claim_frame(other_frame.clone()), Err(other_frame.clone()));
+        command_dispatcher.set_fallback_handler(forwarding_handler(sender, "fallback"));
+        assert!(command_dispatcher.claim_frame(other_frame).is_ok());
+
+        // Responses belong to the request client
+        let response_frame = ProtocolMessage::new(MessageKind::Response, 3, 1, Vec::new()).to_bytes();
+        assert_eq!(command_dispatcher.claim_frame(response_frame.clone()), Err(response_frame));
+    }
+}
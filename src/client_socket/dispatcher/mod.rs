@@ -0,0 +1,2 @@
+pub mod command_dispatch_queue;
+pub mod command_dispatcher;
\ No newline at end of file
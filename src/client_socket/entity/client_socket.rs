@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use futures_util::StreamExt;
+use tokio::io::split;
+use tokio::sync::{watch, Mutex};
+use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_status::HeartbeatStatus;
+use crate::client_socket::entity::socket_connection::{ClientWebSocketStream, SocketConnection};
+use crate::client_socket::entity::socket_stream::{BoxedSocketStream, SharedSocketStream};
+use crate::client_socket::entity::transport::Transport;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::transport::frame_reader::FrameReader;
+use crate::client_socket::transport::frame_writer::FrameWriter;
+
+#[derive(Debug)]
+pub struct ClientSocket {
+    config: ClientSocketConfig,
+    state_sender: watch::Sender<ClientSocketState>,
+    stream: Option<SharedSocketStream>,
+    websocket_stream: Option<ClientWebSocketStream>,
+    frame_reader: Option<FrameReader>,
+    frame_writer: Option<FrameWriter>,
+    heartbeat_status: HeartbeatStatus,
+}
+
+impl ClientSocket {
+    pub fn new(config: ClientSocketConfig) -> Self {
+        ClientSocket {
+            config,
+            state_sender: watch::Sender::new(ClientSocketState::Disconnected),
+            stream: None,
+            websocket_stream: None,
+            frame_reader: None,
+            frame_writer: None,
+            heartbeat_status: HeartbeatStatus::new(),
+        }
+    }
+
+    pub fn get_config(&self) -> &ClientSocketConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: ClientSocketConfig) {
+        self.config = config;
+    }
+
+    pub fn get_state(&self) -> ClientSocketState {
+        *self.state_sender.borrow()
+    }
+
+    pub fn set_state(&mut self, state: ClientSocketState) {
+        self.state_sender.send_replace(state);
+    }
+
+    pub fn subscribe_state(&self) -> watch::Receiver<ClientSocketState> {
+        self.state_sender.subscribe()
+    }
+
+    // Raw stream access only makes sense for the TCP transport; WebSocket traffic goes through the frame reader and writer
+    pub fn get_stream(&self) -> Result<SharedSocketStream, ClientSocketError> {
+        if let Some(stream) = &self.stream {
+            return Ok(stream.clone());
+        }
+
+        if self.websocket_stream.is_some() || self.frame_reader.as_ref().is_some_and(FrameReader::is_websocket) {
+            return Err(ClientSocketError::UnsupportedTransport("raw stream access needs the TCP transport".to_string()));
+        }
+
+        Err(ClientSocketError::NotConnected)
+    }
+
+    pub fn set_stream(&mut self, stream: BoxedSocketStream) {
+        self.set_connection(SocketConnection::Stream(stream));
+    }
+
+    pub fn set_connection(&mut self, connection: SocketConnection) {
+        self.clear_connection();
+        match connection {
+            SocketConnection::Stream(stream) => self.stream = Some(Arc::new(Mutex::new(stream))),
+            SocketConnection::WebSocket(websocket_stream) => self.websocket_stream = Some(*websocket_stream),
+        }
+        self.heartbeat_status.reset_missed_pongs();
+        self.set_state(ClientSocketState::Connected);
+    }
+
+    pub fn take_stream(&mut self) -> Option<SharedSocketStream> {
+        self.set_state(ClientSocketState::Disconnected);
+        let stream = self.stream.take();
+        self.clear_connection();
+        stream
+    }
+
+    // Called by whichever socket worker notices the link is gone first; a deliberate disconnect stays disconnected
+    pub fn mark_connection_lost(&mut self) {
+        if self.get_state() != ClientSocketState::Connected {
+            return;
+        }
+
+        self.clear_connection();
+        self.set_state(ClientSocketState::Reconnecting);
+    }
+
+    pub fn take_frame_reader(&mut self) -> Result<FrameReader, ClientSocketError> {
+        self.split_connection()?;
+        self.frame_reader.take().ok_or(ClientSocketError::NotConnected)
+    }
+
+    pub fn take_frame_writer(&mut self) -> Result<FrameWriter, ClientSocketError> {
+        self.split_connection()?;
+        self.frame_writer.take().ok_or(ClientSocketError::NotConnected)
+    }
+
+    pub fn get_heartbeat_status(&self) -> HeartbeatStatus {
+        self.heartbeat_status
+    }
+
+    pub fn get_heartbeat_status_mut(&mut self) -> &mut HeartbeatStatus {
+        &mut self.heartbeat_status
+    }
+
+    fn clear_connection(&mut self) {
+        self.stream = None;
+        self.websocket_stream = None;
+        self.frame_reader = None;
+        self.frame_writer = None;
+    }
+
+    // Splitting needs the stream itself, so it only succeeds while nobody else holds the shared handle
+    fn split_connection(&mut self) -> Result<(), ClientSocketError> {
+        if let Some(websocket_stream) = self.websocket_stream.take() {
+            let Transport::WebSocket(websocket_config) = self.config.get_transport() else {
+                return Err(ClientSocketError::UnsupportedTransport("WebSocket connection saved for a TCP configuration".to_string()));
+            };
+
+            let (websocket_sink, websocket_stream) = websocket_stream.split();
+            self.frame_reader = Some(FrameReader::WebSocket(websocket_stream));
+            self.frame_writer = Some(FrameWriter::WebSocket {
+                websocket_sink,
+                message_type: websocket_config.get_outgoing_message_type(),
+                max_frame_size: self.config.get_frame_format().get_max_frame_size(),
+            });
+            return Ok(());
+        }
+
+        let Some(stream) = self.stream.take() else {
+            return Ok(());
+        };
+
+        match Arc::try_unwrap(stream) {
+            Ok(stream) => {
+                let (read_half, write_half) = split(stream.into_inner());
+                let frame_format = self.config.get_frame_format();
+                self.frame_reader = Some(FrameReader::Stream { read_half, codec: LengthPrefixedCodec::new(frame_format) });
+                self.frame_writer = Some(FrameWriter::Stream { write_half, codec: LengthPrefixedCodec::new(frame_format) });
+                Ok(())
+            }
+            Err(stream) => {
+                self.stream = Some(stream);
+                Err(ClientSocketError::StreamInUse)
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use tokio::sync::mpsc;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+// Application side of the receiver/transmitter workers
+#[derive(Debug)]
+pub struct ClientSocketChannels {
+    transmit_sender: mpsc::Sender<Vec<u8>>,
+    receive_receiver: mpsc::Receiver<Vec<u8>>,
+    max_message_size: u64,
+}
+
+impl ClientSocketChannels {
+    pub fn new(transmit_sender: mpsc::Sender<Vec<u8>>, receive_receiver: mpsc::Receiver<Vec<u8>>) -> Self {
+        ClientSocketChannels {
+            transmit_sender,
+            receive_receiver,
+            max_message_size: u64::MAX,
+        }
+    }
+
+    pub fn with_max_message_size(mut self, max_message_size: u64) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    pub fn get_max_message_size(&self) -> u64 {
+        self.max_message_size
+    }
+
+    pub fn get_transmit_sender(&self) -> mpsc::Sender<Vec<u8>> {
+        self.transmit_sender.clone()
+    }
+
+    // Rejects a message the transmitter could never send, instead of letting it be dropped there
+    pub async fn send(&self, message: Vec<u8>) -> Result<(), ClientSocketError> {
+        if message.len() as u64 > self.max_message_size {
+            return Err(ClientSocketError::FrameTooLarge { size: message.len() as u64, max_size: self.max_message_size });
+        }
+
+        self.transmit_sender.send(message).await.map_err(|_| ClientSocketError::ChannelClosed)
+    }
+
+    pub async fn receive(&mut self) -> Option<Vec<u8>> {
+        self.receive_receiver.recv().await
+    }
+
+    pub fn into_parts(self) -> (mpsc::Sender<Vec<u8>>, mpsc::Receiver<Vec<u8>>) {
+        (self.transmit_sender, self.receive_receiver)
+    }
+}
@@ -0,0 +1,140 @@
+use std::env;
+use std::time::Duration;
+use crate::client_socket::entity::frame_format::FrameFormat;
+use crate::client_socket::entity::transport::Transport;
+use crate::client_socket::entity::websocket_config::WebSocketConfig;
+#[cfg(feature = "tls")]
+use crate::client_socket::entity::tls_config::TlsConfig;
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 7373;
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientSocketConfig {
+    host: String,
+    port: u16,
+    connect_timeout: Duration,
+    frame_format: FrameFormat,
+    transport: Transport,
+    #[cfg(feature = "tls")]
+    tls_config: Option<TlsConfig>,
+}
+
+impl ClientSocketConfig {
+    pub fn new(host: &str, port: u16) -> Self {
+        ClientSocketConfig {
+            host: host.to_string(),
+            port,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            frame_format: FrameFormat::default(),
+            transport: Transport::default(),
+            #[cfg(feature = "tls")]
+            tls_config: None,
+        }
+    }
+
+    // CLIENT_SOCKET_HOST / CLIENT_SOCKET_PORT override the defaults; an unparsable port is ignored.
+    // CLIENT_SOCKET_WEBSOCKET_PATH switches to the WebSocket transport on that path.
+    pub fn from_env() -> Self {
+        let host = env::var("CLIENT_SOCKET_HOST").unwrap_or_else(|_| DEFAULT_HOST.to_string());
+        let port = env::var("CLIENT_SOCKET_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(DEFAULT_PORT);
+        let transport = env::var("CLIENT_SOCKET_WEBSOCKET_PATH")
+            .map(|path| Transport::WebSocket(WebSocketConfig::new(&path)))
+            .unwrap_or_default();
+
+        ClientSocketConfig::new(&host, port).with_transport(transport)
+    }
+
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    pub fn with_frame_format(mut self, frame_format: FrameFormat) -> Self {
+        self.frame_format = frame_format;
+        self
+    }
+
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn get_connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    pub fn get_frame_format(&self) -> FrameFormat {
+        self.frame_format
+    }
+
+    pub fn get_transport(&self) -> &Transport {
+        &self.transport
+    }
+
+    // Largest payload one frame can carry on the configured transport
+    pub fn get_max_message_size(&self) -> u64 {
+        match self.transport {
+            Transport::Tcp => self.frame_format.get_max_payload_size(),
+            Transport::WebSocket(_) => self.frame_format.get_max_frame_size() as u64,
+        }
+    }
+
+    #[cfg(feature = "tls")]
+    pub fn get_tls_config(&self) -> Option<&TlsConfig> {
+        self.tls_config.as_ref()
+    }
+
+    #[cfg(feature = "tls")]
+    pub fn uses_tls(&self) -> bool {
+        self.tls_config.is_some()
+    }
+
+    #[cfg(not(feature = "tls"))]
+    pub fn uses_tls(&self) -> bool {
+        false
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+impl Default for ClientSocketConfig {
+    fn default() -> Self {
+        ClientSocketConfig::new(DEFAULT_HOST, DEFAULT_PORT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let config = ClientSocketConfig::new("localhost", 9000);
+
+        assert_eq!(config.address(), "localhost:9000");
+        assert_eq!(config.get_connect_timeout(), DEFAULT_CONNECT_TIMEOUT);
+        assert_eq!(ClientSocketConfig::default().address(), "127.0.0.1:7373");
+        assert_eq!(config.get_transport(), &Transport::Tcp);
+    }
+}
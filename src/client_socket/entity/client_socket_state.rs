@@ -0,0 +1,13 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientSocketState {
+    Disconnected,
+    Connecting,
+    Connected,
+    Reconnecting,
+}
+
+impl ClientSocketState {
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ClientSocketState::Connected)
+    }
+}
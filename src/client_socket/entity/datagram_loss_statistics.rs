@@ -0,0 +1,26 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DatagramLossStatistics {
+    received_count: u64,
+    lost_count: u64,
+    late_count: u64,
+}
+
+impl DatagramLossStatistics {
+    pub fn new(received_count: u64, lost_count: u64, late_count: u64) -> Self {
+        DatagramLossStatistics { received_count, lost_count, late_count }
+    }
+
+    pub fn get_received_count(&self) -> u64 {
+        self.received_count
+    }
+
+    // Sequence numbers skipped over; a datagram that shows up later is counted as late, not taken back out of here
+    pub fn get_lost_count(&self) -> u64 {
+        self.lost_count
+    }
+
+    // Reordered or duplicated datagrams that arrived behind a newer one
+    pub fn get_late_count(&self) -> u64 {
+        self.late_count
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::entity::sequence_observation::SequenceObservation;
+use crate::client_socket::entity::sequence_tracker::SequenceTracker;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+#[derive(Debug)]
+pub struct DatagramSocket {
+    config: DatagramSocketConfig,
+    socket: Option<Arc<UdpSocket>>,
+    next_sequence_number: u64,
+    sequence_tracker: SequenceTracker,
+}
+
+impl DatagramSocket {
+    pub fn new(config: DatagramSocketConfig) -> Self {
+        DatagramSocket {
+            config,
+            socket: None,
+            next_sequence_number: 0,
+            sequence_tracker: SequenceTracker::new(),
+        }
+    }
+
+    pub fn get_config(&self) -> &DatagramSocketConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: DatagramSocketConfig) {
+        self.config = config;
+    }
+
+    // UdpSocket sends and receives through &self, so both workers share the same socket
+    pub fn get_socket(&self) -> Result<Arc<UdpSocket>, ClientSocketError> {
+        self.socket.clone().ok_or(ClientSocketError::NotConnected)
+    }
+
+    // A new socket starts a new numbering on both directions
+    pub fn set_socket(&mut self, socket: UdpSocket) {
+        self.socket = Some(Arc::new(socket));
+        self.next_sequence_number = 0;
+        self.sequence_tracker = SequenceTracker::new();
+    }
+
+    pub fn take_socket(&mut self) -> Option<Arc<UdpSocket>> {
+        self.socket.take()
+    }
+
+    pub fn next_sequence_number(&mut self) -> u64 {
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number = self.next_sequence_number.wrapping_add(1);
+        sequence_number
+    }
+
+    pub fn observe_sequence_number(&mut self, sequence_number: u64) -> SequenceObservation {
+        self.sequence_tracker.observe(sequence_number)
+    }
+
+    pub fn resync_sequence_numbers(&mut self) {
+        self.sequence_tracker.resync();
+    }
+
+    pub fn get_loss_statistics(&self) -> DatagramLossStatistics {
+        self.sequence_tracker.get_statistics()
+    }
+}
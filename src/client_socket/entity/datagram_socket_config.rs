@@ -0,0 +1,96 @@
+use std::env;
+use crate::client_socket::codec::sequence_number_codec::SEQUENCE_NUMBER_SIZE;
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 7374;
+const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:0";
+// Largest payload an IPv4 UDP datagram can carry
+const DEFAULT_MAX_DATAGRAM_SIZE: usize = 65_507;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatagramSocketConfig {
+    host: String,
+    port: u16,
+    bind_address: String,
+    max_datagram_size: usize,
+    sequence_numbers: bool,
+}
+
+impl DatagramSocketConfig {
+    pub fn new(host: &str, port: u16) -> Self {
+        DatagramSocketConfig {
+            host: host.to_string(),
+            port,
+            bind_address: DEFAULT_BIND_ADDRESS.to_string(),
+            max_datagram_size: DEFAULT_MAX_DATAGRAM_SIZE,
+            sequence_numbers: false,
+        }
+    }
+
+    // CLIENT_DATAGRAM_HOST / CLIENT_DATAGRAM_PORT override the defaults; an unparsable port is ignored
+    pub fn from_env() -> Self {
+        let host = env::var("CLIENT_DATAGRAM_HOST").unwrap_or_else(|_| DEFAULT_HOST.to_string());
+        let port = env::var("CLIENT_DATAGRAM_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(DEFAULT_PORT);
+
+        DatagramSocketConfig::new(&host, port)
+    }
+
+    pub fn with_bind_address(mut self, bind_address: &str) -> Self {
+        self.bind_address = bind_address.to_string();
+        self
+    }
+
+    // Counts the whole datagram, sequence number included
+    pub fn with_max_datagram_size(mut self, max_datagram_size: usize) -> Self {
+        self.max_datagram_size = max_datagram_size;
+        self
+    }
+
+    // Both sides must agree: every datagram then starts with a u64 big endian sequence number
+    pub fn with_sequence_numbers(mut self, sequence_numbers: bool) -> Self {
+        self.sequence_numbers = sequence_numbers;
+        self
+    }
+
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn get_bind_address(&self) -> &str {
+        &self.bind_address
+    }
+
+    pub fn get_max_datagram_size(&self) -> usize {
+        self.max_datagram_size
+    }
+
+    // What is left of a datagram once the sequence number header, if any, is in
+    pub fn get_max_payload_size(&self) -> usize {
+        if self.sequence_numbers {
+            self.max_datagram_size.saturating_sub(SEQUENCE_NUMBER_SIZE)
+        } else {
+            self.max_datagram_size
+        }
+    }
+
+    pub fn uses_sequence_numbers(&self) -> bool {
+        self.sequence_numbers
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+impl Default for DatagramSocketConfig {
+    fn default() -> Self {
+        DatagramSocketConfig::new(DEFAULT_HOST, DEFAULT_PORT)
+    }
+}
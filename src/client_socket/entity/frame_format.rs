@@ -0,0 +1,78 @@
+const DEFAULT_MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthFieldSize {
+    Two,
+    Four,
+    Eight,
+}
+
+impl LengthFieldSize {
+    pub fn byte_count(&self) -> usize {
+        match self {
+            LengthFieldSize::Two => 2,
+            LengthFieldSize::Four => 4,
+            LengthFieldSize::Eight => 8,
+        }
+    }
+
+    pub fn max_length(&self) -> u64 {
+        match self {
+            LengthFieldSize::Two => u16::MAX as u64,
+            LengthFieldSize::Four => u32::MAX as u64,
+            LengthFieldSize::Eight => u64::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    BigEndian,
+    LittleEndian,
+}
+
+// Header layout of the frames exchanged with the server; the length counts payload bytes only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameFormat {
+    length_field_size: LengthFieldSize,
+    byte_order: ByteOrder,
+    max_frame_size: usize,
+}
+
+impl FrameFormat {
+    pub fn new(length_field_size: LengthFieldSize, byte_order: ByteOrder) -> Self {
+        FrameFormat {
+            length_field_size,
+            byte_order,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    pub fn get_length_field_size(&self) -> LengthFieldSize {
+        self.length_field_size
+    }
+
+    pub fn get_byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    pub fn get_max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    // The configured maximum, unless the length field is too small to describe it
+    pub fn get_max_payload_size(&self) -> u64 {
+        (self.max_frame_size as u64).min(self.length_field_size.max_length())
+    }
+}
+
+impl Default for FrameFormat {
+    fn default() -> Self {
+        FrameFormat::new(LengthFieldSize::Four, ByteOrder::BigEndian)
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+// Reserved command code the server answers with an empty response
+pub const DEFAULT_HEARTBEAT_COMMAND_CODE: u16 = 0xFFFF;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(15);
+const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_MISSED_PONGS: u32 = 3;
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatConfig {
+    interval: Duration,
+    pong_timeout: Duration,
+    max_missed_pongs: u32,
+    command_code: u16,
+}
+
+impl HeartbeatConfig {
+    pub fn new() -> Self {
+        HeartbeatConfig {
+            interval: DEFAULT_INTERVAL,
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
+            max_missed_pongs: DEFAULT_MAX_MISSED_PONGS,
+            command_code: DEFAULT_HEARTBEAT_COMMAND_CODE,
+        }
+    }
+
+    // Time between pings; zero is treated as one millisecond so the ping timer can still tick
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(MIN_INTERVAL);
+        self
+    }
+
+    pub fn with_pong_timeout(mut self, pong_timeout: Duration) -> Self {
+        self.pong_timeout = pong_timeout;
+        self
+    }
+
+    // Consecutive misses before the link is declared dead; zero is treated as one
+    pub fn with_max_missed_pongs(mut self, max_missed_pongs: u32) -> Self {
+        self.max_missed_pongs = max_missed_pongs.max(1);
+        self
+    }
+
+    pub fn with_command_code(mut self, command_code: u16) -> Self {
+        self.command_code = command_code;
+        self
+    }
+
+    pub fn get_interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn get_pong_timeout(&self) -> Duration {
+        self.pong_timeout
+    }
+
+    pub fn get_max_missed_pongs(&self) -> u32 {
+        self.max_missed_pongs
+    }
+
+    pub fn get_command_code(&self) -> u16 {
+        self.command_code
+    }
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        HeartbeatConfig::new()
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HeartbeatStatus {
+    last_round_trip_time: Option<Duration>,
+    smoothed_round_trip_time: Option<Duration>,
+    consecutive_missed_pongs: u32,
+    total_missed_pongs: u64,
+}
+
+impl HeartbeatStatus {
+    pub fn new() -> Self {
+        HeartbeatStatus::default()
+    }
+
+    // Smoothed the way TCP does it: each sample moves the estimate by an eighth
+    pub fn record_pong(&mut self, round_trip_time: Duration) {
+        self.last_round_trip_time = Some(round_trip_time);
+        self.smoothed_round_trip_time = Some(match self.smoothed_round_trip_time {
+            Some(smoothed) => smoothed.mul_f64(0.875) + round_trip_time.mul_f64(0.125),
+            None => round_trip_time,
+        });
+        self.consecutive_missed_pongs = 0;
+    }
+
+    pub fn record_missed_pong(&mut self) -> u32 {
+        self.consecutive_missed_pongs += 1;
+        self.total_missed_pongs += 1;
+        self.consecutive_missed_pongs
+    }
+
+    // A fresh connection has not missed anything yet; the history stays
+    pub fn reset_missed_pongs(&mut self) {
+        self.consecutive_missed_pongs = 0;
+    }
+
+    pub fn get_last_round_trip_time(&self) -> Option<Duration> {
+        self.last_round_trip_time
+    }
+
+    pub fn get_smoothed_round_trip_time(&self) -> Option<Duration> {
+        self.smoothed_round_trip_time
+    }
+
+    pub fn get_consecutive_missed_pongs(&self) -> u32 {
+        self.consecutive_missed_pongs
+    }
+
+    pub fn get_total_missed_pongs(&self) -> u64 {
+        self.total_missed_pongs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_pong_and_missed_pong() {
+        let mut heartbeat_status = HeartbeatStatus::new();
+
+        assert_eq!(heartbeat_status.record_missed_pong(), 1);
+        assert_eq!(heartbeat_status.record_missed_pong(), 2);
+
+        heartbeat_status.record_pong(Duration::from_millis(80));
+        heartbeat_status.record_pong(Duration::from_millis(160));
+
+        assert_eq!(heartbeat_status.get_last_round_trip_time(), Some(Duration::from_millis(160)));
+        assert_eq!(heartbeat_status.get_smoothed_round_trip_time(), Some(Duration::from_millis(90)));
+        assert_eq!(heartbeat_status.get_consecutive_missed_pongs(), 0);
+        assert_eq!(heartbeat_status.get_total_missed_pongs(), 2);
+    }
+}
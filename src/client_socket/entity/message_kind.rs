@@ -0,0 +1,26 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    Request,
+    Response,
+    // Pushed by either side without expecting an answer
+    Event,
+}
+
+impl MessageKind {
+    pub fn to_byte(self) -> u8 {
+        match self {
+            MessageKind::Request => 0,
+            MessageKind::Response => 1,
+            MessageKind::Event => 2,
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(MessageKind::Request),
+            1 => Some(MessageKind::Response),
+            2 => Some(MessageKind::Event),
+            _ => None,
+        }
+    }
+}
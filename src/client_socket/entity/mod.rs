@@ -0,0 +1,23 @@
+pub mod client_socket;
+pub mod client_socket_channels;
+pub mod client_socket_config;
+pub mod client_socket_state;
+pub mod datagram_loss_statistics;
+pub mod datagram_socket;
+pub mod datagram_socket_config;
+pub mod frame_format;
+pub mod heartbeat_config;
+pub mod heartbeat_status;
+pub mod message_kind;
+pub mod pending_request_table;
+pub mod protocol_message;
+pub mod reconnect_policy;
+pub mod sequence_observation;
+pub mod sequence_tracker;
+pub mod socket_connection;
+pub mod socket_stream;
+#[cfg(feature = "tls")]
+pub mod tls_config;
+pub mod transport;
+pub mod websocket_config;
+pub mod websocket_message_type;
\ No newline at end of file
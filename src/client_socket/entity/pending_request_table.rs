@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::oneshot;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+
+// Requests waiting for their response, keyed by correlation id
+#[derive(Debug, Clone, Default)]
+pub struct PendingRequestTable {
+    pending_request_list: Arc<Mutex<HashMap<u64, oneshot::Sender<ProtocolMessage>>>>,
+}
+
+impl PendingRequestTable {
+    pub fn new() -> Self {
+        PendingRequestTable::default()
+    }
+
+    pub fn insert(&self, correlation_id: u64) -> oneshot::Receiver<ProtocolMessage> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.lock().insert(correlation_id, response_sender);
+        response_receiver
+    }
+
+    // Hands the response to its waiting request; returns it back when nobody is waiting for that id
+    pub fn complete(&self, response: ProtocolMessage) -> Result<(), ProtocolMessage> {
+        match self.lock().remove(&response.get_correlation_id()) {
+            Some(response_sender) => response_sender.send(response),
+            None => Err(response),
+        }
+    }
+
+    pub fn remove(&self, correlation_id: u64) {
+        self.lock().remove(&correlation_id);
+    }
+
+    // Dropping the senders wakes every waiting request with a closed channel
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<ProtocolMessage>>> {
+        // Every operation is a single map call, so a poisoned map is still consistent
+        self.pending_request_list.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::client_socket::entity::message_kind::MessageKind;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+// kind (1 byte) | command code (2 bytes, big endian) | correlation id (8 bytes, big endian) | payload
+const HEADER_SIZE: usize = 11;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolMessage {
+    message_kind: MessageKind,
+    command_code: u16,
+    correlation_id: u64,
+    payload: Vec<u8>,
+}
+
+impl ProtocolMessage {
+    pub fn new(message_kind: MessageKind, command_code: u16, correlation_id: u64, payload: Vec<u8>) -> Self {
+        ProtocolMessage { message_kind, command_code, correlation_id, payload }
+    }
+
+    // The correlation id is assigned when the request is sent
+    pub fn request(command_code: u16, payload: Vec<u8>) -> Self {
+        ProtocolMessage::new(MessageKind::Request, command_code, 0, payload)
+    }
+
+    pub fn event(command_code: u16, payload: Vec<u8>) -> Self {
+        ProtocolMessage::new(MessageKind::Event, command_code, 0, payload)
+    }
+
+    pub fn response_to(request: &ProtocolMessage, payload: Vec<u8>) -> Self {
+        ProtocolMessage::new(MessageKind::Response, request.command_code, request.correlation_id, payload)
+    }
+
+    pub fn get_message_kind(&self) -> MessageKind {
+        self.message_kind
+    }
+
+    pub fn get_command_code(&self) -> u16 {
+        self.command_code
+    }
+
+    pub fn get_correlation_id(&self) -> u64 {
+        self.correlation_id
+    }
+
+    pub fn set_correlation_id(&mut self, correlation_id: u64) {
+        self.correlation_id = correlation_id;
+    }
+
+    pub fn get_payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    pub fn into_payload(self) -> Vec<u8> {
+        self.payload
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.payload.len());
+        bytes.push(self.message_kind.to_byte());
+        bytes.extend_from_slice(&self.command_code.to_be_bytes());
+        bytes.extend_from_slice(&self.correlation_id.to_be_bytes());
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ClientSocketError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(ClientSocketError::InvalidMessage(format!("{} bytes is shorter than the message header", bytes.len())));
+        }
+
+        let message_kind = MessageKind::from_byte(bytes[0])
+            .ok_or_else(|| ClientSocketError::InvalidMessage(format!("unknown message kind {}", bytes[0])))?;
+        let command_code = u16::from_be_bytes([bytes[1], bytes[2]]);
+        let mut correlation_id = [0u8; 8];
+        correlation_id.copy_from_slice(&bytes[3..HEADER_SIZE]);
+
+        Ok(ProtocolMessage::new(message_kind, command_code, u64::from_be_bytes(correlation_id), bytes[HEADER_SIZE..].to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut request = ProtocolMessage::request(0x0102, b"payload".to_vec());
+        request.set_correlation_id(7);
+
+        let bytes = request.to_bytes();
+        assert_eq!(&bytes[..HEADER_SIZE], &[0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 7]);
+        assert_eq!(ProtocolMessage::from_bytes(&bytes).unwrap(), request);
+
+        let response = ProtocolMessage::response_to(&request, Vec::new());
+        assert_eq!(response.get_message_kind(), MessageKind::Response);
+        assert_eq!(response.get_correlation_id(), 7);
+        assert_eq!(response.get_command_code(), 0x0102);
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        assert!(matches!(ProtocolMessage::from_bytes(&[1, 2, 3]), Err(ClientSocketError::InvalidMessage(_))));
+        assert!(matches!(ProtocolMessage::from_bytes(&[9; HEADER_SIZE]), Err(ClientSocketError::InvalidMessage(_))));
+    }
+}
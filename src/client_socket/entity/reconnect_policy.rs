@@ -0,0 +1,133 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+const DEFAULT_MULTIPLIER: f64 = 2.0;
+const DEFAULT_JITTER: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: f64,
+    max_attempts: Option<u32>,
+}
+
+impl ReconnectPolicy {
+    pub fn new() -> Self {
+        ReconnectPolicy {
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            multiplier: DEFAULT_MULTIPLIER,
+            jitter: DEFAULT_JITTER,
+            max_attempts: None,
+        }
+    }
+
+    // A multiplier below 1.0 would shrink the backoff or flip its sign, so it is treated as 1.0
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration, multiplier: f64) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    // 0.0 waits exactly the exponential backoff, 1.0 waits anywhere between zero and that backoff
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn get_max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn is_exhausted(&self, attempt_count: u32) -> bool {
+        self.max_attempts.is_some_and(|max_attempts| attempt_count >= max_attempts)
+    }
+
+    // Backoff before retry number `attempt_count` (1-based), without jitter
+    pub fn base_backoff_for(&self, attempt_count: u32) -> Duration {
+        let exponent = attempt_count.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+
+        // Rounding near Duration::MAX can still leave the capped value out of range
+        Duration::try_from_secs_f64(backoff.min(self.max_backoff.as_secs_f64())).unwrap_or(self.max_backoff)
+    }
+
+    pub fn backoff_for(&self, attempt_count: u32) -> Duration {
+        // Spread retries so many clients dropped at once do not reconnect in lockstep
+        self.base_backoff_for(attempt_count).mul_f64(1.0 - self.jitter * random_unit())
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy::new()
+    }
+}
+
+// Uniform value in [0, 1) from the randomly seeded std hasher, to avoid pulling in a rand dependency
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.subsec_nanos()).unwrap_or_default();
+    hasher.write_u32(nanos);
+
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_backoff_grows_and_caps() {
+        let policy = ReconnectPolicy::new().with_backoff(Duration::from_millis(100), Duration::from_millis(500), 2.0);
+
+        assert_eq!(policy.base_backoff_for(1), Duration::from_millis(100));
+        assert_eq!(policy.base_backoff_for(2), Duration::from_millis(200));
+        assert_eq!(policy.base_backoff_for(3), Duration::from_millis(400));
+        assert_eq!(policy.base_backoff_for(4), Duration::from_millis(500));
+        assert_eq!(policy.base_backoff_for(100), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_base_backoff_survives_bad_multipliers_and_huge_caps() {
+        let shrinking = ReconnectPolicy::new().with_backoff(Duration::from_millis(100), Duration::from_secs(1), -2.0);
+        assert_eq!(shrinking.base_backoff_for(3), Duration::from_millis(100));
+
+        let unbounded = ReconnectPolicy::new().with_backoff(Duration::from_secs(1), Duration::MAX, 10.0);
+        assert_eq!(unbounded.base_backoff_for(u32::MAX), Duration::MAX);
+    }
+
+    #[test]
+    fn test_jittered_backoff_stays_in_range() {
+        let policy = ReconnectPolicy::new()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1), 2.0)
+            .with_jitter(0.5);
+
+        for _ in 0..100 {
+            let backoff = policy.backoff_for(2);
+            assert!(backoff > Duration::from_millis(100) && backoff <= Duration::from_millis(200));
+        }
+
+        assert_eq!(policy.clone().with_jitter(0.0).backoff_for(2), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let policy = ReconnectPolicy::new().with_max_attempts(3);
+
+        assert!(!policy.is_exhausted(2));
+        assert!(policy.is_exhausted(3));
+        assert!(!ReconnectPolicy::new().is_exhausted(u32::MAX));
+    }
+}
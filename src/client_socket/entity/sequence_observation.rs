@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceObservation {
+    InOrder,
+    Gap { missing_count: u64 },
+    Late,
+    Restarted,
+}
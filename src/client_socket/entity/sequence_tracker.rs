@@ -0,0 +1,99 @@
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::entity::sequence_observation::SequenceObservation;
+
+// A number this far behind is a peer that started counting again, not a datagram that took the long way
+const RESTART_DISTANCE: u64 = 1024;
+
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    next_expected: Option<u64>,
+    received_count: u64,
+    lost_count: u64,
+    late_count: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        SequenceTracker::default()
+    }
+
+    // The first sequence number seen becomes the baseline, so a peer may start counting anywhere
+    pub fn observe(&mut self, sequence_number: u64) -> SequenceObservation {
+        self.received_count += 1;
+
+        match self.next_expected {
+            Some(next_expected) if sequence_number < next_expected && next_expected - sequence_number > RESTART_DISTANCE => {
+                self.next_expected = Some(sequence_number.wrapping_add(1));
+                SequenceObservation::Restarted
+            }
+            Some(next_expected) if sequence_number < next_expected => {
+                self.late_count += 1;
+                SequenceObservation::Late
+            }
+            Some(next_expected) if sequence_number > next_expected => {
+                let missing_count = sequence_number - next_expected;
+                self.lost_count += missing_count;
+                self.next_expected = Some(sequence_number.wrapping_add(1));
+                SequenceObservation::Gap { missing_count }
+            }
+            _ => {
+                self.next_expected = Some(sequence_number.wrapping_add(1));
+                SequenceObservation::InOrder
+            }
+        }
+    }
+
+    // Forgets the baseline but keeps the counts, so the next number seen is taken as in order
+    pub fn resync(&mut self) {
+        self.next_expected = None;
+    }
+
+    pub fn get_statistics(&self) -> DatagramLossStatistics {
+        DatagramLossStatistics::new(self.received_count, self.lost_count, self.late_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe() {
+        let mut sequence_tracker = SequenceTracker::new();
+
+        assert_eq!(sequence_tracker.observe(10), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(11), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(14), SequenceObservation::Gap { missing_count: 2 });
+        assert_eq!(sequence_tracker.observe(12), SequenceObservation::Late);
+        assert_eq!(sequence_tracker.observe(14), SequenceObservation::Late);
+        assert_eq!(sequence_tracker.observe(15), SequenceObservation::InOrder);
+
+        assert_eq!(sequence_tracker.get_statistics(), DatagramLossStatistics::new(6, 2, 2));
+    }
+
+    #[test]
+    fn test_observe_restarted_peer() {
+        let mut sequence_tracker = SequenceTracker::new();
+
+        assert_eq!(sequence_tracker.observe(5000), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(0), SequenceObservation::Restarted);
+        assert_eq!(sequence_tracker.observe(1), SequenceObservation::InOrder);
+
+        // A restart close to where the peer was is only caught by resyncing on purpose
+        assert_eq!(sequence_tracker.observe(2), SequenceObservation::InOrder);
+        sequence_tracker.resync();
+        assert_eq!(sequence_tracker.observe(0), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(1), SequenceObservation::InOrder);
+
+        assert_eq!(sequence_tracker.get_statistics(), DatagramLossStatistics::new(6, 0, 0));
+    }
+
+    #[test]
+    fn test_observe_last_sequence_number() {
+        let mut sequence_tracker = SequenceTracker::new();
+
+        assert_eq!(sequence_tracker.observe(u64::MAX - 1), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(u64::MAX), SequenceObservation::InOrder);
+        assert_eq!(sequence_tracker.observe(0), SequenceObservation::InOrder);
+    }
+}
@@ -0,0 +1,10 @@
+use tokio_tungstenite::WebSocketStream;
+use crate::client_socket::entity::socket_stream::BoxedSocketStream;
+
+pub type ClientWebSocketStream = WebSocketStream<BoxedSocketStream>;
+
+#[derive(Debug)]
+pub enum SocketConnection {
+    Stream(BoxedSocketStream),
+    WebSocket(Box<ClientWebSocketStream>),
+}
@@ -0,0 +1,14 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
+use tokio::sync::Mutex;
+
+// Anything the client can talk to the server over: a plain TcpStream or, with the `tls` feature, a TLS stream on top of it
+pub trait SocketStream: AsyncRead + AsyncWrite + Debug + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Debug + Send + Unpin> SocketStream for T {}
+
+pub type BoxedSocketStream = Box<dyn SocketStream>;
+pub type SharedSocketStream = Arc<Mutex<BoxedSocketStream>>;
+pub type SocketReadHalf = ReadHalf<BoxedSocketStream>;
+pub type SocketWriteHalf = WriteHalf<BoxedSocketStream>;
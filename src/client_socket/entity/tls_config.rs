@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    certificate_chain_pem: Vec<u8>,
+    private_key_pem: Vec<u8>,
+}
+
+impl ClientCertificate {
+    pub fn get_certificate_chain_pem(&self) -> &[u8] {
+        &self.certificate_chain_pem
+    }
+
+    pub fn get_private_key_pem(&self) -> &[u8] {
+        &self.private_key_pem
+    }
+}
+
+// Trust is anchored in the configured CA bundles; pins additionally require the server's own
+// certificate to match one of the SHA-256 fingerprints, and are enough on their own for self-signed servers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    ca_certificate_pem_list: Vec<Vec<u8>>,
+    client_certificate: Option<ClientCertificate>,
+    server_name: Option<String>,
+    pinned_certificate_sha256_list: Vec<[u8; 32]>,
+}
+
+impl TlsConfig {
+    pub fn new() -> Self {
+        TlsConfig::default()
+    }
+
+    pub fn with_ca_certificate_pem(mut self, ca_certificate_pem: &[u8]) -> Self {
+        self.ca_certificate_pem_list.push(ca_certificate_pem.to_vec());
+        self
+    }
+
+    pub fn with_ca_certificate_file(self, path: impl AsRef<Path>) -> Result<Self, ClientSocketError> {
+        let ca_certificate_pem = read_pem_file(path.as_ref())?;
+        Ok(self.with_ca_certificate_pem(&ca_certificate_pem))
+    }
+
+    pub fn with_client_certificate_pem(mut self, certificate_chain_pem: &[u8], private_key_pem: &[u8]) -> Self {
+        self.client_certificate = Some(ClientCertificate {
+            certificate_chain_pem: certificate_chain_pem.to_vec(),
+            private_key_pem: private_key_pem.to_vec(),
+        });
+        self
+    }
+
+    pub fn with_client_certificate_file(self, certificate_chain_path: impl AsRef<Path>, private_key_path: impl AsRef<Path>) -> Result<Self, ClientSocketError> {
+        let certificate_chain_pem = read_pem_file(certificate_chain_path.as_ref())?;
+        let private_key_pem = read_pem_file(private_key_path.as_ref())?;
+        Ok(self.with_client_certificate_pem(&certificate_chain_pem, &private_key_pem))
+    }
+
+    // Name sent as SNI and checked against the certificate, instead of the configured host
+    pub fn with_server_name(mut self, server_name: &str) -> Self {
+        self.server_name = Some(server_name.to_string());
+        self
+    }
+
+    pub fn with_pinned_certificate_sha256(mut self, certificate_sha256: [u8; 32]) -> Self {
+        self.pinned_certificate_sha256_list.push(certificate_sha256);
+        self
+    }
+
+    pub fn get_ca_certificate_pem_list(&self) -> &[Vec<u8>] {
+        &self.ca_certificate_pem_list
+    }
+
+    pub fn get_client_certificate(&self) -> Option<&ClientCertificate> {
+        self.client_certificate.as_ref()
+    }
+
+    pub fn get_server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    pub fn get_pinned_certificate_sha256_list(&self) -> &[[u8; 32]] {
+        &self.pinned_certificate_sha256_list
+    }
+}
+
+fn read_pem_file(path: &Path) -> Result<Vec<u8>, ClientSocketError> {
+    fs::read(path).map_err(|error| ClientSocketError::TlsConfig(format!("cannot read {}: {}", path.display(), error)))
+}
@@ -0,0 +1,9 @@
+use crate::client_socket::entity::websocket_config::WebSocketConfig;
+
+// How frames travel over the connection: length-prefixed on the raw stream, or one WebSocket message per frame
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Transport {
+    #[default]
+    Tcp,
+    WebSocket(WebSocketConfig),
+}
@@ -0,0 +1,35 @@
+use crate::client_socket::entity::websocket_message_type::WebSocketMessageType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketConfig {
+    path: String,
+    outgoing_message_type: WebSocketMessageType,
+}
+
+impl WebSocketConfig {
+    pub fn new(path: &str) -> Self {
+        WebSocketConfig {
+            path: path.to_string(),
+            outgoing_message_type: WebSocketMessageType::default(),
+        }
+    }
+
+    pub fn with_outgoing_message_type(mut self, outgoing_message_type: WebSocketMessageType) -> Self {
+        self.outgoing_message_type = outgoing_message_type;
+        self
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_outgoing_message_type(&self) -> WebSocketMessageType {
+        self.outgoing_message_type
+    }
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        WebSocketConfig::new("/")
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WebSocketMessageType {
+    #[default]
+    Binary,
+    // Outgoing payloads must then be valid UTF-8
+    Text,
+}
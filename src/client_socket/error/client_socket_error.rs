@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::PoisonError;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientSocketError {
+    NotConnected,
+    StreamInUse,
+    UnsupportedTransport(String),
+    Io(String),
+    WebSocket(String),
+    ChannelClosed,
+    FrameTooLarge { size: u64, max_size: u64 },
+    InvalidMessage(String),
+    RequestTimeout(u64),
+    UnknownCommand(u16),
+    AlreadyConnected(String),
+    ConnectFailed { address: String, message: String },
+    ConnectTimeout(String),
+    TlsConfig(String),
+    TlsHandshake { address: String, message: String },
+    LockPoisoned(String),
+    Worker(ThreadWorkerError),
+}
+
+impl fmt::Display for ClientSocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientSocketError::NotConnected => write!(f, "Client socket is not connected"),
+            ClientSocketError::StreamInUse => write!(f, "Client socket stream is still shared and cannot be split"),
+            ClientSocketError::UnsupportedTransport(message) => write!(f, "Not supported by this transport: {}", message),
+            ClientSocketError::Io(message) => write!(f, "Client socket I/O error: {}", message),
+            ClientSocketError::WebSocket(message) => write!(f, "WebSocket error: {}", message),
+            ClientSocketError::ChannelClosed => write!(f, "Client socket channel closed"),
+            ClientSocketError::InvalidMessage(message) => write!(f, "Invalid protocol message: {}", message),
+            ClientSocketError::RequestTimeout(correlation_id) => write!(f, "Request {} timed out waiting for its response", correlation_id),
+            ClientSocketError::UnknownCommand(command_code) => write!(f, "No handler for command {}", command_code),
+            ClientSocketError::FrameTooLarge { size, max_size } => write!(f, "Frame of {} bytes exceeds the {} byte limit", size, max_size),
+            ClientSocketError::AlreadyConnected(address) => write!(f, "Client socket already connected to {}", address),
+            ClientSocketError::ConnectFailed { address, message } => write!(f, "Failed to connect to {}: {}", address, message),
+            ClientSocketError::ConnectTimeout(address) => write!(f, "Timed out connecting to {}", address),
+            ClientSocketError::TlsConfig(message) => write!(f, "Invalid TLS configuration: {}", message),
+            ClientSocketError::TlsHandshake { address, message } => write!(f, "TLS handshake with {} failed: {}", address, message),
+            ClientSocketError::LockPoisoned(message) => write!(f, "Client socket lock poisoned: {}", message),
+            ClientSocketError::Worker(error) => write!(f, "Client socket worker error: {}", error),
+        }
+    }
+}
+
+impl Error for ClientSocketError {}
+
+impl<T> From<PoisonError<T>> for ClientSocketError {
+    fn from(error: PoisonError<T>) -> Self {
+        ClientSocketError::LockPoisoned(error.to_string())
+    }
+}
+
+impl From<ThreadWorkerError> for ClientSocketError {
+    fn from(error: ThreadWorkerError) -> Self {
+        ClientSocketError::Worker(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ClientSocketError::ConnectTimeout("127.0.0.1:7373".to_string()).to_string(),
+            "Timed out connecting to 127.0.0.1:7373"
+        );
+        assert_eq!(
+            ClientSocketError::from(ThreadWorkerError::NotFound("Receiver".to_string())).to_string(),
+            "Client socket worker error: Thread worker not found: Receiver"
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod client_socket_error;
\ No newline at end of file
@@ -0,0 +1,11 @@
+pub mod codec;
+pub mod connector;
+pub mod dispatcher;
+pub mod entity;
+pub mod error;
+pub mod reconnector;
+pub mod repository;
+pub mod request;
+pub mod service;
+pub mod transport;
+pub mod worker;
\ No newline at end of file
@@ -0,0 +1,234 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use crate::client_socket::connector::socket_connector::SocketConnector;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::reconnect_policy::ReconnectPolicy;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+use crate::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use crate::client_socket::worker::receiver_worker_factory::RECEIVER_WORKER_NAME;
+use crate::client_socket::worker::transmitter_worker_factory::TRANSMITTER_WORKER_NAME;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+const SOCKET_WORKER_NAMES: [&str; 2] = [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME];
+
+pub struct ClientSocketReconnector {
+    client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+    thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    reconnect_policy: ReconnectPolicy,
+}
+
+impl ClientSocketReconnector {
+    pub fn new(
+        client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+        thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+        reconnect_policy: ReconnectPolicy,
+    ) -> Self {
+        ClientSocketReconnector { client_socket_repository, thread_worker_repository, reconnect_policy }
+    }
+
+    // Watches for the socket workers reporting a lost link and brings the connection and the workers back.
+    // Runs until the thread worker repository starts shutting down.
+    pub fn start(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            self.run().await;
+        })
+    }
+
+    async fn run(self) {
+        let shutdown_token = self.thread_worker_repository.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_shutdown_token();
+        let mut state_receiver = self.client_socket_repository.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .subscribe_state();
+
+        loop {
+            let connection_lost = tokio::select! {
+                _ = shutdown_token.cancelled() => return,
+                wait_result = state_receiver.wait_for(|state| *state == ClientSocketState::Reconnecting) => wait_result.is_ok(),
+            };
+
+            if !connection_lost {
+                return;
+            }
+
+            self.stop_socket_workers().await;
+
+            if !self.reconnect(&shutdown_token).await {
+                return;
+            }
+        }
+    }
+
+    // Returns false only when shutdown interrupted the attempts
+    async fn reconnect(&self, shutdown_token: &CancellationToken) -> bool {
+        let mut attempt_count = 0;
+
+        loop {
+            let config = {
+                let repository = self.client_socket_repository.lock().unwrap_or_else(PoisonError::into_inner);
+
+                // Someone disconnected on purpose while we were retrying
+                if repository.get_state() != ClientSocketState::Reconnecting {
+                    return true;
+                }
+                repository.get_config()
+            };
+
+            let connect_result = tokio::select! {
+                _ = shutdown_token.cancelled() => return false,
+                connect_result = SocketConnector::connect(&config) => connect_result,
+            };
+
+            match connect_result {
+                Ok(connection) => {
+                    {
+                        let mut repository = self.client_socket_repository.lock().unwrap_or_else(PoisonError::into_inner);
+                        if repository.get_state() != ClientSocketState::Reconnecting {
+                            return true;
+                        }
+                        repository.save_connection(connection);
+                    }
+
+                    self.start_socket_workers();
+                    return true;
+                }
+                Err(error) => {
+                    attempt_count += 1;
+
+                    if self.reconnect_policy.is_exhausted(attempt_count) {
+                        eprintln!("Giving up reconnecting to {} after {} attempts: {}", config.address(), attempt_count, error);
+                        self.client_socket_repository.lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .set_state(ClientSocketState::Disconnected);
+                        return true;
+                    }
+
+                    tokio::select! {
+                        _ = shutdown_token.cancelled() => return false,
+                        _ = sleep(self.reconnect_policy.backoff_for(attempt_count)) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    // The worker that saw the failure has already exited; the other one is still blocked on its half
+    async fn stop_socket_workers(&self) {
+        let worker_handle_list: Vec<_> = {
+            let mut repository = self.thread_worker_repository.lock().unwrap_or_else(PoisonError::into_inner);
+
+            SOCKET_WORKER_NAMES.iter()
+                .filter_map(|name| {
+                    repository.stop_thread_worker(name).ok()?;
+                    repository.find_worker_handle(name)
+                })
+                .collect()
+        };
+
+        for worker_handle in worker_handle_list {
+            worker_handle.join().await;
+        }
+    }
+
+    fn start_socket_workers(&self) {
+        let mut repository = self.thread_worker_repository.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for name in SOCKET_WORKER_NAMES {
+            if repository.find_by_name(name).is_none() {
+                continue;
+            }
+
+            if let Err(error) = repository.start_thread_worker(name) {
+                eprintln!("Failed to restart {} after reconnecting: {}", name, error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+    use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+    use crate::client_socket::entity::frame_format::FrameFormat;
+    use crate::client_socket::worker::receiver_worker_factory::ReceiverWorkerFactory;
+    use crate::client_socket::worker::transmitter_worker_factory::TransmitterWorkerFactory;
+
+    fn fast_policy() -> ReconnectPolicy {
+        ReconnectPolicy::new().with_backoff(Duration::from_millis(10), Duration::from_millis(50), 2.0)
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_restarts_socket_workers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()))));
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+
+        let mut message_receiver = ReceiverWorkerFactory::new(Arc::clone(&client_socket_repository)).register(&thread_worker_repository).unwrap();
+        let message_sender = TransmitterWorkerFactory::new(Arc::clone(&client_socket_repository)).register(&thread_worker_repository).unwrap();
+
+        let client_stream = TcpStream::connect(address).await.unwrap();
+        client_socket_repository.lock().unwrap().save_stream(Box::new(client_stream));
+        let (first_server_stream, _) = listener.accept().await.unwrap();
+
+        for name in SOCKET_WORKER_NAMES {
+            thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+
+        let reconnector = ClientSocketReconnector::new(
+            Arc::clone(&client_socket_repository),
+            Arc::clone(&thread_worker_repository),
+            fast_policy(),
+        ).start();
+
+        // Server drops the link; the client should come back on its own
+        drop(first_server_stream);
+        let (mut second_server_stream, _) = listener.accept().await.unwrap();
+
+        let mut state_receiver = client_socket_repository.lock().unwrap().subscribe_state();
+        state_receiver.wait_for(|state| *state == ClientSocketState::Connected).await.unwrap();
+
+        message_sender.send(b"back".to_vec()).await.unwrap();
+        let codec = LengthPrefixedCodec::new(FrameFormat::default());
+        let mut buffer = [0u8; 8];
+        second_server_stream.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer.to_vec(), codec.encode(b"back").unwrap());
+
+        second_server_stream.write_all(&codec.encode(b"ack").unwrap()).await.unwrap();
+        assert_eq!(message_receiver.recv().await.unwrap(), b"ack".to_vec());
+
+        thread_worker_repository.lock().unwrap().begin_shutdown();
+        reconnector.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        // Bind then drop to get a port nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", port))));
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut state_receiver = client_socket_repository.lock().unwrap().subscribe_state();
+
+        let reconnector = ClientSocketReconnector::new(
+            Arc::clone(&client_socket_repository),
+            Arc::clone(&thread_worker_repository),
+            fast_policy().with_max_attempts(2),
+        ).start();
+
+        client_socket_repository.lock().unwrap().set_state(ClientSocketState::Reconnecting);
+        state_receiver.wait_for(|state| *state == ClientSocketState::Disconnected).await.unwrap();
+
+        thread_worker_repository.lock().unwrap().begin_shutdown();
+        reconnector.await.unwrap();
+    }
+}
@@ -0,0 +1 @@
+pub mod client_socket_reconnector;
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::time::Duration;
+use tokio::sync::watch;
+use crate::client_socket::dispatcher::command_dispatcher::CommandDispatcher;
+use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_status::HeartbeatStatus;
+use crate::client_socket::entity::socket_connection::SocketConnection;
+use crate::client_socket::entity::socket_stream::{BoxedSocketStream, SharedSocketStream};
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::transport::frame_reader::FrameReader;
+use crate::client_socket::transport::frame_writer::FrameWriter;
+
+pub trait ClientSocketRepositoryTrait {
+    fn save_config(&mut self, config: ClientSocketConfig);
+    fn get_config(&self) -> ClientSocketConfig;
+    fn get_state(&self) -> ClientSocketState;
+    fn set_state(&mut self, state: ClientSocketState);
+    fn subscribe_state(&self) -> watch::Receiver<ClientSocketState>;
+    fn mark_connection_lost(&mut self);
+    fn save_stream(&mut self, stream: BoxedSocketStream);
+    fn save_connection(&mut self, connection: SocketConnection);
+    fn get_stream(&self) -> Result<SharedSocketStream, ClientSocketError>;
+    fn take_stream(&mut self) -> Option<SharedSocketStream>;
+    fn take_frame_reader(&mut self) -> Result<FrameReader, ClientSocketError>;
+    fn take_frame_writer(&mut self) -> Result<FrameWriter, ClientSocketError>;
+    fn get_command_dispatcher(&self) -> CommandDispatcher;
+    fn record_pong(&mut self, round_trip_time: Duration);
+    fn record_missed_pong(&mut self) -> u32;
+    fn get_heartbeat_status(&self) -> HeartbeatStatus;
+}
@@ -0,0 +1,179 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use lazy_static::lazy_static;
+use tokio::sync::watch;
+use crate::client_socket::dispatcher::command_dispatcher::CommandDispatcher;
+use crate::client_socket::entity::client_socket::ClientSocket;
+use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_status::HeartbeatStatus;
+use crate::client_socket::entity::socket_connection::SocketConnection;
+use crate::client_socket::entity::socket_stream::{BoxedSocketStream, SharedSocketStream};
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::transport::frame_reader::FrameReader;
+use crate::client_socket::transport::frame_writer::FrameWriter;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+
+pub struct ClientSocketRepositoryImpl {
+    client_socket: ClientSocket,
+    command_dispatcher: CommandDispatcher,
+}
+
+impl ClientSocketRepositoryImpl {
+    pub fn new(config: ClientSocketConfig) -> Self {
+        ClientSocketRepositoryImpl {
+            client_socket: ClientSocket::new(config),
+            command_dispatcher: CommandDispatcher::new(),
+        }
+    }
+
+    pub fn get_instance() -> Arc<Mutex<ClientSocketRepositoryImpl>> {
+        lazy_static! {
+            static ref INSTANCE: Arc<Mutex<ClientSocketRepositoryImpl>> =
+                Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::from_env())));
+        }
+        INSTANCE.clone()
+    }
+
+    pub fn get_client_socket(&self) -> &ClientSocket {
+        &self.client_socket
+    }
+}
+
+impl ClientSocketRepositoryTrait for ClientSocketRepositoryImpl {
+    fn save_config(&mut self, config: ClientSocketConfig) {
+        self.client_socket.set_config(config);
+    }
+
+    fn get_config(&self) -> ClientSocketConfig {
+        self.client_socket.get_config().clone()
+    }
+
+    fn get_state(&self) -> ClientSocketState {
+        self.client_socket.get_state()
+    }
+
+    fn set_state(&mut self, state: ClientSocketState) {
+        self.client_socket.set_state(state);
+    }
+
+    fn subscribe_state(&self) -> watch::Receiver<ClientSocketState> {
+        self.client_socket.subscribe_state()
+    }
+
+    fn mark_connection_lost(&mut self) {
+        self.client_socket.mark_connection_lost();
+    }
+
+    fn save_stream(&mut self, stream: BoxedSocketStream) {
+        self.client_socket.set_stream(stream);
+    }
+
+    fn save_connection(&mut self, connection: SocketConnection) {
+        self.client_socket.set_connection(connection);
+    }
+
+    fn get_stream(&self) -> Result<SharedSocketStream, ClientSocketError> {
+        self.client_socket.get_stream()
+    }
+
+    fn take_stream(&mut self) -> Option<SharedSocketStream> {
+        self.client_socket.take_stream()
+    }
+
+    fn take_frame_reader(&mut self) -> Result<FrameReader, ClientSocketError> {
+        self.client_socket.take_frame_reader()
+    }
+
+    fn take_frame_writer(&mut self) -> Result<FrameWriter, ClientSocketError> {
+        self.client_socket.take_frame_writer()
+    }
+
+    fn get_command_dispatcher(&self) -> CommandDispatcher {
+        self.command_dispatcher.clone()
+    }
+
+    fn record_pong(&mut self, round_trip_time: Duration) {
+        self.client_socket.get_heartbeat_status_mut().record_pong(round_trip_time);
+    }
+
+    fn record_missed_pong(&mut self) -> u32 {
+        self.client_socket.get_heartbeat_status_mut().record_missed_pong()
+    }
+
+    fn get_heartbeat_status(&self) -> HeartbeatStatus {
+        self.client_socket.get_heartbeat_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_singleton() {
+        let instance1 = ClientSocketRepositoryImpl::get_instance();
+        let instance2 = ClientSocketRepositoryImpl::get_instance();
+
+        assert!(Arc::ptr_eq(&instance1, &instance2));
+    }
+
+    #[tokio::test]
+    async fn test_save_and_take_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut repository = ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()));
+
+        assert_eq!(repository.get_state(), ClientSocketState::Disconnected);
+        assert_eq!(repository.get_stream().unwrap_err(), ClientSocketError::NotConnected);
+
+        let stream = TcpStream::connect(address).await.unwrap();
+        repository.save_stream(Box::new(stream));
+
+        assert_eq!(repository.get_state(), ClientSocketState::Connected);
+        assert!(repository.get_stream().is_ok());
+
+        assert!(repository.take_stream().is_some());
+        assert_eq!(repository.get_state(), ClientSocketState::Disconnected);
+        assert_eq!(repository.get_stream().unwrap_err(), ClientSocketError::NotConnected);
+    }
+
+    #[tokio::test]
+    async fn test_take_frame_reader_and_writer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut repository = ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()));
+
+        assert_eq!(repository.take_frame_reader().unwrap_err(), ClientSocketError::NotConnected);
+
+        repository.save_stream(Box::new(TcpStream::connect(address).await.unwrap()));
+
+        let shared_stream = repository.get_stream().unwrap();
+        assert_eq!(repository.take_frame_reader().unwrap_err(), ClientSocketError::StreamInUse);
+        drop(shared_stream);
+
+        assert!(repository.take_frame_reader().is_ok());
+        assert!(repository.take_frame_writer().is_ok());
+        assert_eq!(repository.take_frame_writer().unwrap_err(), ClientSocketError::NotConnected);
+        assert_eq!(repository.get_state(), ClientSocketState::Connected);
+    }
+
+    #[tokio::test]
+    async fn test_mark_connection_lost() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut repository = ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()));
+        let state_receiver = repository.subscribe_state();
+
+        // Nothing to lose while disconnected
+        repository.mark_connection_lost();
+        assert_eq!(*state_receiver.borrow(), ClientSocketState::Disconnected);
+
+        repository.save_stream(Box::new(TcpStream::connect(address).await.unwrap()));
+        repository.mark_connection_lost();
+
+        assert_eq!(*state_receiver.borrow(), ClientSocketState::Reconnecting);
+        assert_eq!(repository.get_stream().unwrap_err(), ClientSocketError::NotConnected);
+    }
+}
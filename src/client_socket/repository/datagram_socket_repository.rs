@@ -0,0 +1,18 @@
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::entity::sequence_observation::SequenceObservation;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+pub trait DatagramSocketRepositoryTrait {
+    fn save_config(&mut self, config: DatagramSocketConfig);
+    fn get_config(&self) -> DatagramSocketConfig;
+    fn save_socket(&mut self, socket: UdpSocket);
+    fn get_socket(&self) -> Result<Arc<UdpSocket>, ClientSocketError>;
+    fn take_socket(&mut self) -> Option<Arc<UdpSocket>>;
+    fn next_sequence_number(&mut self) -> u64;
+    fn observe_sequence_number(&mut self, sequence_number: u64) -> SequenceObservation;
+    fn resync_sequence_numbers(&mut self);
+    fn get_loss_statistics(&self) -> DatagramLossStatistics;
+}
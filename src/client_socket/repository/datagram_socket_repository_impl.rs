@@ -0,0 +1,100 @@
+use std::sync::{Arc, Mutex};
+use lazy_static::lazy_static;
+use tokio::net::UdpSocket;
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::entity::datagram_socket::DatagramSocket;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::entity::sequence_observation::SequenceObservation;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::datagram_socket_repository::DatagramSocketRepositoryTrait;
+
+pub struct DatagramSocketRepositoryImpl {
+    datagram_socket: DatagramSocket,
+}
+
+impl DatagramSocketRepositoryImpl {
+    pub fn new(config: DatagramSocketConfig) -> Self {
+        DatagramSocketRepositoryImpl {
+            datagram_socket: DatagramSocket::new(config),
+        }
+    }
+
+    pub fn get_instance() -> Arc<Mutex<DatagramSocketRepositoryImpl>> {
+        lazy_static! {
+            static ref INSTANCE: Arc<Mutex<DatagramSocketRepositoryImpl>> =
+                Arc::new(Mutex::new(DatagramSocketRepositoryImpl::new(DatagramSocketConfig::from_env())));
+        }
+        INSTANCE.clone()
+    }
+}
+
+impl DatagramSocketRepositoryTrait for DatagramSocketRepositoryImpl {
+    fn save_config(&mut self, config: DatagramSocketConfig) {
+        self.datagram_socket.set_config(config);
+    }
+
+    fn get_config(&self) -> DatagramSocketConfig {
+        self.datagram_socket.get_config().clone()
+    }
+
+    fn save_socket(&mut self, socket: UdpSocket) {
+        self.datagram_socket.set_socket(socket);
+    }
+
+    fn get_socket(&self) -> Result<Arc<UdpSocket>, ClientSocketError> {
+        self.datagram_socket.get_socket()
+    }
+
+    fn take_socket(&mut self) -> Option<Arc<UdpSocket>> {
+        self.datagram_socket.take_socket()
+    }
+
+    fn next_sequence_number(&mut self) -> u64 {
+        self.datagram_socket.next_sequence_number()
+    }
+
+    fn observe_sequence_number(&mut self, sequence_number: u64) -> SequenceObservation {
+        self.datagram_socket.observe_sequence_number(sequence_number)
+    }
+
+    fn resync_sequence_numbers(&mut self) {
+        self.datagram_socket.resync_sequence_numbers();
+    }
+
+    fn get_loss_statistics(&self) -> DatagramLossStatistics {
+        self.datagram_socket.get_loss_statistics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_singleton() {
+        let instance1 = DatagramSocketRepositoryImpl::get_instance();
+        let instance2 = DatagramSocketRepositoryImpl::get_instance();
+
+        assert!(Arc::ptr_eq(&instance1, &instance2));
+    }
+
+    #[tokio::test]
+    async fn test_new_socket_restarts_sequence_numbers() {
+        let mut repository = DatagramSocketRepositoryImpl::new(DatagramSocketConfig::new("127.0.0.1", 9));
+        assert_eq!(repository.get_socket().unwrap_err(), ClientSocketError::NotConnected);
+
+        repository.save_socket(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        assert_eq!(repository.next_sequence_number(), 0);
+        assert_eq!(repository.next_sequence_number(), 1);
+        repository.observe_sequence_number(5);
+        repository.observe_sequence_number(7);
+        assert_eq!(repository.get_loss_statistics(), DatagramLossStatistics::new(2, 1, 0));
+
+        repository.save_socket(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        assert_eq!(repository.next_sequence_number(), 0);
+        assert_eq!(repository.get_loss_statistics(), DatagramLossStatistics::default());
+
+        assert!(repository.take_socket().is_some());
+        assert_eq!(repository.get_socket().unwrap_err(), ClientSocketError::NotConnected);
+    }
+}
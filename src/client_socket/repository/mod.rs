@@ -0,0 +1,4 @@
+pub mod client_socket_repository;
+pub mod client_socket_repository_impl;
+pub mod datagram_socket_repository;
+pub mod datagram_socket_repository_impl;
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod request_client;
\ No newline at end of file
@@ -0,0 +1,264 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::time::timeout;
+use crate::client_socket::entity::client_socket_channels::ClientSocketChannels;
+use crate::client_socket::entity::message_kind::MessageKind;
+use crate::client_socket::entity::pending_request_table::PendingRequestTable;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct RequestClient {
+    transmit_sender: mpsc::Sender<Vec<u8>>,
+    pending_request_table: PendingRequestTable,
+    next_correlation_id: Arc<AtomicU64>,
+    request_timeout: Duration,
+    max_message_size: u64,
+}
+
+impl RequestClient {
+    // Takes over the socket channels. Responses go to the request that is waiting for them;
+    // requests and events from the server come out of the returned receiver.
+    pub fn start(channels: ClientSocketChannels) -> (RequestClient, mpsc::Receiver<ProtocolMessage>) {
+        let max_message_size = channels.get_max_message_size();
+        let (transmit_sender, receive_receiver) = channels.into_parts();
+        let (incoming_sender, incoming_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+        let pending_request_table = PendingRequestTable::new();
+
+        tokio::spawn(route_incoming(receive_receiver, pending_request_table.clone(), incoming_sender));
+
+        let request_client = RequestClient {
+            transmit_sender,
+            pending_request_table,
+            // Zero stays free for messages that are not part of a request
+            next_correlation_id: Arc::new(AtomicU64::new(1)),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_message_size,
+        };
+
+        (request_client, incoming_receiver)
+    }
+
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    pub fn get_pending_request_count(&self) -> usize {
+        self.pending_request_table.len()
+    }
+
+    pub async fn request(&self, message: ProtocolMessage) -> Result<ProtocolMessage, ClientSocketError> {
+        self.request_with_timeout(message, self.request_timeout).await
+    }
+
+    pub async fn request_with_timeout(&self, mut message: ProtocolMessage, request_timeout: Duration) -> Result<ProtocolMessage, ClientSocketError> {
+        let correlation_id = self.next_correlation_id.fetch_add(1, Ordering::Relaxed);
+        message.set_correlation_id(correlation_id);
+
+        let response_receiver = self.pending_request_table.insert(correlation_id);
+        // Clears the entry on every way out, including the caller dropping this future
+        let _pending_request_guard = PendingRequestGuard { pending_request_table: &self.pending_request_table, correlation_id };
+
+        self.send(message).await?;
+
+        match timeout(request_timeout, response_receiver).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(ClientSocketError::ChannelClosed),
+            Err(_) => Err(ClientSocketError::RequestTimeout(correlation_id)),
+        }
+    }
+
+    // Sends without waiting for an answer, e.g. events or responses to server requests
+    pub async fn send(&self, message: ProtocolMessage) -> Result<(), ClientSocketError> {
+        let frame = message.to_bytes();
+        if frame.len() as u64 > self.max_message_size {
+            return Err(ClientSocketError::FrameTooLarge { size: frame.len() as u64, max_size: self.max_message_size });
+        }
+
+        self.transmit_sender.send(frame).await.map_err(|_| ClientSocketError::ChannelClosed)
+    }
+}
+
+struct PendingRequestGuard<'a> {
+    pending_request_table: &'a PendingRequestTable,
+    correlation_id: u64,
+}
+
+impl Drop for PendingRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.pending_request_table.remove(self.correlation_id);
+    }
+}
+
+async fn route_incoming(
+    mut receive_receiver: mpsc::Receiver<Vec<u8>>,
+    pending_request_table: PendingRequestTable,
+    incoming_sender: mpsc::Sender<ProtocolMessage>,
+) {
+    while let Some(frame) = receive_receiver.recv().await {
+        let message = match ProtocolMessage::from_bytes(&frame) {
+            Ok(message) => message,
+            Err(error) => {
+                eprintln!("Dropping incoming frame: {}", error);
+                continue;
+            }
+        };
+
+        if message.get_message_kind() == MessageKind::Response {
+            if let Err(response) = pending_request_table.complete(message) {
+                eprintln!("Dropping response {} nobody is waiting for", response.get_correlation_id());
+            }
+            continue;
+        }
+
+        // Waiting for room here would hold up every response queued behind this message,
+        // so an application that falls behind on unsolicited messages loses them instead
+        if let Err(TrySendError::Full(message)) = incoming_sender.try_send(message) {
+            eprintln!("Dropping unsolicited message with command code {}: the receiver is full", message.get_command_code());
+        }
+    }
+
+    pending_request_table.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeServer {
+        outgoing_receiver: mpsc::Receiver<Vec<u8>>,
+        incoming_sender: mpsc::Sender<Vec<u8>>,
+    }
+
+    fn start_request_client() -> (RequestClient, mpsc::Receiver<ProtocolMessage>, FakeServer) {
+        let (transmit_sender, outgoing_receiver) = mpsc::channel(16);
+        let (incoming_sender, receive_receiver) = mpsc::channel(16);
+
+        let (request_client, unsolicited_receiver) = RequestClient::start(ClientSocketChannels::new(transmit_sender, receive_receiver));
+        (request_client, unsolicited_receiver, FakeServer { outgoing_receiver, incoming_sender })
+    }
+
+    impl FakeServer {
+        async fn next_request(&mut self) -> ProtocolMessage {
+            ProtocolMessage::from_bytes(&self.outgoing_receiver.recv().await.unwrap()).unwrap()
+        }
+
+        async fn reply(&self, message: ProtocolMessage) {
+            self.incoming_sender.send(message.to_bytes()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interleaved_responses_reach_their_requests() {
+        let (request_client, mut unsolicited_receiver, mut fake_server) = start_request_client();
+
+        let first_client = request_client.clone();
+        let first = tokio::spawn(async move { first_client.request(ProtocolMessage::request(1, b"first".to_vec())).await });
+        let first_request = fake_server.next_request().await;
+
+        let second_client = request_client.clone();
+        let second = tokio::spawn(async move { second_client.request(ProtocolMessage::request(2, b"second".to_vec())).await });
+        let second_request = fake_server.next_request().await;
+
+        assert_ne!(first_request.get_correlation_id(), second_request.get_correlation_id());
+
+        // Answer out of order with an unrelated event in between
+        fake_server.reply(ProtocolMessage::response_to(&second_request, b"to second".to_vec())).await;
+        fake_server.reply(ProtocolMessage::event(9, b"news".to_vec())).await;
+        fake_server.reply(ProtocolMessage::response_to(&first_request, b"to first".to_vec())).await;
+
+        assert_eq!(first.await.unwrap().unwrap().get_payload(), b"to first");
+        assert_eq!(second.await.unwrap().unwrap().get_payload(), b"to second");
+        assert_eq!(unsolicited_receiver.recv().await.unwrap().get_payload(), b"news");
+        assert_eq!(request_client.get_pending_request_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_responses_are_routed_while_unsolicited_receiver_is_full() {
+        let (request_client, mut unsolicited_receiver, mut fake_server) = start_request_client();
+
+        for _ in 0..DEFAULT_CHANNEL_CAPACITY + 1 {
+            fake_server.reply(ProtocolMessage::event(9, b"news".to_vec())).await;
+        }
+
+        let request = tokio::spawn({
+            let request_client = request_client.clone();
+            async move { request_client.request(ProtocolMessage::request(1, Vec::new())).await }
+        });
+        let pending_request = fake_server.next_request().await;
+        fake_server.reply(ProtocolMessage::response_to(&pending_request, b"answer".to_vec())).await;
+
+        let response = timeout(Duration::from_secs(5), request).await.expect("the response was stuck behind unsolicited messages");
+        assert_eq!(response.unwrap().unwrap().get_payload(), b"answer");
+
+        let mut unsolicited_count = 0;
+        while unsolicited_receiver.try_recv().is_ok() {
+            unsolicited_count += 1;
+        }
+        assert_eq!(unsolicited_count, DEFAULT_CHANNEL_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_messages_are_rejected_before_queueing() {
+        let (transmit_sender, mut outgoing_receiver) = mpsc::channel(16);
+        let (_incoming_sender, receive_receiver) = mpsc::channel(16);
+        let channels = ClientSocketChannels::new(transmit_sender, receive_receiver).with_max_message_size(16);
+
+        assert_eq!(channels.send(vec![0; 17]).await.unwrap_err(), ClientSocketError::FrameTooLarge { size: 17, max_size: 16 });
+
+        let (request_client, _unsolicited_receiver) = RequestClient::start(channels);
+        let oversized_message = ProtocolMessage::event(1, vec![0; 16]);
+        let oversized_size = oversized_message.to_bytes().len() as u64;
+        assert_eq!(
+            request_client.request(oversized_message).await.unwrap_err(),
+            ClientSocketError::FrameTooLarge { size: oversized_size, max_size: 16 }
+        );
+        assert_eq!(request_client.get_pending_request_count(), 0);
+
+        request_client.send(ProtocolMessage::event(1, Vec::new())).await.unwrap();
+        assert!(outgoing_receiver.try_recv().is_ok());
+        assert!(outgoing_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_timeout() {
+        let (request_client, _unsolicited_receiver, mut fake_server) = start_request_client();
+        let request_client = request_client.with_request_timeout(Duration::from_millis(100));
+
+        let request = tokio::spawn({
+            let request_client = request_client.clone();
+            async move { request_client.request(ProtocolMessage::request(1, Vec::new())).await }
+        });
+        let unanswered_request = fake_server.next_request().await;
+
+        assert_eq!(
+            request.await.unwrap().unwrap_err(),
+            ClientSocketError::RequestTimeout(unanswered_request.get_correlation_id())
+        );
+        assert_eq!(request_client.get_pending_request_count(), 0);
+
+        // A response arriving after the timeout is simply dropped
+        fake_server.reply(ProtocolMessage::response_to(&unanswered_request, Vec::new())).await;
+    }
+
+    #[tokio::test]
+    async fn test_pending_requests_fail_when_connection_channel_closes() {
+        let (request_client, _unsolicited_receiver, mut fake_server) = start_request_client();
+
+        let request = tokio::spawn({
+            let request_client = request_client.clone();
+            async move { request_client.request(ProtocolMessage::request(1, Vec::new())).await }
+        });
+        fake_server.next_request().await;
+        drop(fake_server);
+
+        assert_eq!(request.await.unwrap().unwrap_err(), ClientSocketError::ChannelClosed);
+    }
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use crate::client_socket::dispatcher::command_dispatcher::CommandHandler;
+use crate::client_socket::entity::client_socket_channels::ClientSocketChannels;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_config::HeartbeatConfig;
+use crate::client_socket::entity::heartbeat_status::HeartbeatStatus;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+use crate::client_socket::entity::socket_stream::SharedSocketStream;
+use crate::client_socket::entity::reconnect_policy::ReconnectPolicy;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::request::request_client::RequestClient;
+use crate::thread_control::entity::thread_worker::ThreadWorkerFuture;
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+
+pub type ClientSocketWorkerFunction = Box<dyn Fn(SharedSocketStream, ThreadWorkerContext) -> ThreadWorkerFuture + Send + Sync + 'static>;
+
+#[async_trait]
+pub trait ClientSocketServiceTrait {
+    async fn connect(&self) -> Result<(), ClientSocketError>;
+    fn disconnect(&self) -> Result<(), ClientSocketError>;
+    fn get_state(&self) -> Result<ClientSocketState, ClientSocketError>;
+    fn get_stream(&self) -> Result<SharedSocketStream, ClientSocketError>;
+    fn save_connection_worker(&self, name: &str, will_be_execute_function: ClientSocketWorkerFunction) -> Result<(), ClientSocketError>;
+    fn register_socket_workers(&self) -> Result<ClientSocketChannels, ClientSocketError>;
+    fn register_request_client(&self) -> Result<(RequestClient, mpsc::Receiver<ProtocolMessage>), ClientSocketError>;
+    fn register_heartbeat_worker(&self, request_client: &RequestClient, heartbeat_config: HeartbeatConfig) -> Result<(), ClientSocketError>;
+    fn get_heartbeat_status(&self) -> Result<HeartbeatStatus, ClientSocketError>;
+    fn start_auto_reconnect(&self, reconnect_policy: ReconnectPolicy) -> JoinHandle<()>;
+    fn register_command_handler(&self, command_code: u16, handler: CommandHandler) -> Result<(), ClientSocketError>;
+    fn set_fallback_command_handler(&self, handler: CommandHandler) -> Result<(), ClientSocketError>;
+}
@@ -0,0 +1,527 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use crate::client_socket::connector::socket_connector::SocketConnector;
+use crate::client_socket::dispatcher::command_dispatcher::CommandHandler;
+use crate::client_socket::entity::client_socket_channels::ClientSocketChannels;
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_config::HeartbeatConfig;
+use crate::client_socket::entity::heartbeat_status::HeartbeatStatus;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+use crate::client_socket::entity::socket_stream::SharedSocketStream;
+use crate::client_socket::entity::reconnect_policy::ReconnectPolicy;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::reconnector::client_socket_reconnector::ClientSocketReconnector;
+use crate::client_socket::request::request_client::RequestClient;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+use crate::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use crate::client_socket::service::client_socket_service::{ClientSocketServiceTrait, ClientSocketWorkerFunction};
+use crate::client_socket::worker::heartbeat_worker_factory::{HeartbeatWorkerFactory, HEARTBEAT_WORKER_NAME};
+use crate::client_socket::worker::receiver_worker_factory::{ReceiverWorkerFactory, RECEIVER_WORKER_NAME};
+use crate::client_socket::worker::transmitter_worker_factory::{TransmitterWorkerFactory, TRANSMITTER_WORKER_NAME};
+use crate::thread_control::entity::thread_worker::ThreadWorkerFuture;
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub struct ClientSocketServiceImpl {
+    repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+    thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+}
+
+impl ClientSocketServiceImpl {
+    pub fn new(
+        repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+        thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    ) -> Self {
+        ClientSocketServiceImpl { repository, thread_worker_repository }
+    }
+
+    pub fn get_instance() -> Arc<Mutex<ClientSocketServiceImpl>> {
+        lazy_static! {
+            static ref INSTANCE: Arc<Mutex<ClientSocketServiceImpl>> =
+                Arc::new(Mutex::new(ClientSocketServiceImpl::new(
+                    ClientSocketRepositoryImpl::get_instance(),
+                    ThreadWorkerRepositoryImpl::get_instance(),
+                )));
+        }
+        INSTANCE.clone()
+    }
+}
+
+#[async_trait]
+impl ClientSocketServiceTrait for ClientSocketServiceImpl {
+    async fn connect(&self) -> Result<(), ClientSocketError> {
+        // The repository lock is only held around state changes, never across the connect itself
+        let config = {
+            let mut repository = self.repository.lock()?;
+            let config = repository.get_config();
+
+            if repository.get_state() != ClientSocketState::Disconnected {
+                return Err(ClientSocketError::AlreadyConnected(config.address()));
+            }
+
+            repository.set_state(ClientSocketState::Connecting);
+            config
+        };
+
+        let connect_result = SocketConnector::connect(&config).await;
+
+        let mut repository = self.repository.lock()?;
+        match connect_result {
+            Ok(connection) => {
+                repository.save_connection(connection);
+                Ok(())
+            }
+            Err(error) => {
+                repository.set_state(ClientSocketState::Disconnected);
+                Err(error)
+            }
+        }
+    }
+
+    fn disconnect(&self) -> Result<(), ClientSocketError> {
+        // The transmitter goes first so it can close our side cleanly with the writer it holds
+        {
+            let mut thread_worker_repository = self.thread_worker_repository.lock()?;
+            for name in [TRANSMITTER_WORKER_NAME, RECEIVER_WORKER_NAME, HEARTBEAT_WORKER_NAME] {
+                match thread_worker_repository.stop_thread_worker(name) {
+                    Ok(()) | Err(ThreadWorkerError::NotFound(_)) => {}
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
+
+        // Dropping the last reference closes the socket; connection workers still holding it finish with their copy
+        self.repository.lock()?.take_stream();
+        Ok(())
+    }
+
+    fn get_state(&self) -> Result<ClientSocketState, ClientSocketError> {
+        Ok(self.repository.lock()?.get_state())
+    }
+
+    fn get_stream(&self) -> Result<SharedSocketStream, ClientSocketError> {
+        self.repository.lock()?.get_stream()
+    }
+
+    fn save_connection_worker(&self, name: &str, will_be_execute_function: ClientSocketWorkerFunction) -> Result<(), ClientSocketError> {
+        let repository = Arc::clone(&self.repository);
+        let will_be_execute_function = Arc::new(will_be_execute_function);
+
+        // The stream is looked up when the worker starts, so workers can be registered before connecting
+        let connection_function = move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let stream = repository.lock()
+                .map_err(ClientSocketError::from)
+                .and_then(|repository| repository.get_stream());
+
+            match stream {
+                Ok(stream) => will_be_execute_function(stream, context),
+                // Failing the run lets supervisors and the health report see the worker never got going
+                Err(error) => Box::pin(async move {
+                    context.fail(format!("could not use the client socket: {}", error));
+                }),
+            }
+        };
+
+        self.thread_worker_repository.lock()?.save_thread_worker_with_context(name, Box::new(connection_function));
+        Ok(())
+    }
+
+    fn register_socket_workers(&self) -> Result<ClientSocketChannels, ClientSocketError> {
+        let receive_receiver = ReceiverWorkerFactory::new(Arc::clone(&self.repository)).register(&self.thread_worker_repository)?;
+        let transmit_sender = TransmitterWorkerFactory::new(Arc::clone(&self.repository)).register(&self.thread_worker_repository)?;
+
+        let max_message_size = self.repository.lock()?.get_config().get_max_message_size();
+
+        Ok(ClientSocketChannels::new(transmit_sender, receive_receiver).with_max_message_size(max_message_size))
+    }
+
+    fn register_request_client(&self) -> Result<(RequestClient, mpsc::Receiver<ProtocolMessage>), ClientSocketError> {
+        Ok(RequestClient::start(self.register_socket_workers()?))
+    }
+
+    fn register_heartbeat_worker(&self, request_client: &RequestClient, heartbeat_config: HeartbeatConfig) -> Result<(), ClientSocketError> {
+        HeartbeatWorkerFactory::new(Arc::clone(&self.repository), request_client.clone(), heartbeat_config).register(&self.thread_worker_repository)
+    }
+
+    fn get_heartbeat_status(&self) -> Result<HeartbeatStatus, ClientSocketError> {
+        Ok(self.repository.lock()?.get_heartbeat_status())
+    }
+
+    fn start_auto_reconnect(&self, reconnect_policy: ReconnectPolicy) -> JoinHandle<()> {
+        ClientSocketReconnector::new(Arc::clone(&self.repository), Arc::clone(&self.thread_worker_repository), reconnect_policy).start()
+    }
+
+    fn register_command_handler(&self, command_code: u16, handler: CommandHandler) -> Result<(), ClientSocketError> {
+        self.repository.lock()?.get_command_dispatcher().register_handler(command_code, handler);
+        Ok(())
+    }
+
+    fn set_fallback_command_handler(&self, handler: CommandHandler) -> Result<(), ClientSocketError> {
+        self.repository.lock()?.get_command_dispatcher().set_fallback_handler(handler);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::WebSocketStream;
+    use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+    use crate::client_socket::dispatcher::command_dispatcher::CommandHandlerFuture;
+    use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+    use crate::client_socket::entity::frame_format::FrameFormat;
+    use crate::client_socket::entity::heartbeat_config::DEFAULT_HEARTBEAT_COMMAND_CODE;
+    use crate::client_socket::entity::transport::Transport;
+    use crate::client_socket::entity::websocket_config::WebSocketConfig;
+    use crate::client_socket::entity::websocket_message_type::WebSocketMessageType;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+
+    fn create_service(config: ClientSocketConfig) -> ClientSocketServiceImpl {
+        ClientSocketServiceImpl::new(
+            Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(config))),
+            Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new())),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_connect_and_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port));
+
+        service.connect().await.unwrap();
+        assert_eq!(service.get_state().unwrap(), ClientSocketState::Connected);
+        assert!(matches!(service.connect().await, Err(ClientSocketError::AlreadyConnected(_))));
+
+        service.disconnect().unwrap();
+        assert_eq!(service.get_state().unwrap(), ClientSocketState::Disconnected);
+        assert_eq!(service.get_stream().unwrap_err(), ClientSocketError::NotConnected);
+    }
+
+    #[tokio::test]
+    async fn test_connect_refused() {
+        // Bind then drop to get a port nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port).with_connect_timeout(Duration::from_secs(1)));
+
+        assert!(matches!(service.connect().await, Err(ClientSocketError::ConnectFailed { .. })));
+        assert_eq!(service.get_state().unwrap(), ClientSocketState::Disconnected);
+    }
+
+    #[tokio::test]
+    async fn test_connection_worker_uses_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port));
+
+        let greeting_function = |stream: SharedSocketStream, _context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            Box::pin(async move {
+                stream.lock().await.write_all(b"hello").await.unwrap();
+            })
+        };
+        service.save_connection_worker("GreetingWorker", Box::new(greeting_function)).unwrap();
+
+        service.connect().await.unwrap();
+        let (mut server_stream, _) = listener.accept().await.unwrap();
+
+        let worker_handle = service.thread_worker_repository.lock().unwrap().start_thread_worker("GreetingWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+
+        let mut buffer = [0u8; 5];
+        server_stream.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_connection_worker_fails_without_stream() {
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", 7373));
+
+        let idle_function = |_stream: SharedSocketStream, _context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            Box::pin(async {})
+        };
+        service.save_connection_worker("StreamlessWorker", Box::new(idle_function)).unwrap();
+
+        let worker_handle = service.thread_worker_repository.lock().unwrap().start_thread_worker("StreamlessWorker").unwrap();
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Failed(format!("could not use the client socket: {}", ClientSocketError::NotConnected))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_socket_workers_echo() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port));
+
+        let echo_server = tokio::spawn(async move {
+            let (mut server_stream, _) = listener.accept().await.unwrap();
+            // Four byte length header plus "echo", sent back frame and all
+            let mut buffer = [0u8; 8];
+            server_stream.read_exact(&mut buffer).await.unwrap();
+            server_stream.write_all(&buffer).await.unwrap();
+        });
+
+        let mut channels = service.register_socket_workers().unwrap();
+        service.connect().await.unwrap();
+
+        for name in [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME] {
+            service.thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+
+        channels.send(b"echo".to_vec()).await.unwrap();
+        assert_eq!(channels.receive().await.unwrap(), b"echo".to_vec());
+        echo_server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receiver_dispatches_commands() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port));
+
+        let (handled_sender, mut handled_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let notice_handler = move |message: ProtocolMessage| -> CommandHandlerFuture {
+            let handled_sender = handled_sender.clone();
+            Box::pin(async move {
+                handled_sender.send(message.into_payload()).unwrap();
+            })
+        };
+        service.register_command_handler(42, Arc::new(notice_handler)).unwrap();
+
+        let (request_client, _unsolicited_receiver) = service.register_request_client().unwrap();
+        service.connect().await.unwrap();
+        let (mut server_stream, _) = listener.accept().await.unwrap();
+
+        for name in [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME] {
+            service.thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+
+        let request = tokio::spawn(async move { request_client.request(ProtocolMessage::request(7, Vec::new())).await });
+
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default());
+        let mut buffer = vec![0u8; 64];
+        let request_message = loop {
+            let length = server_stream.read(&mut buffer).await.unwrap();
+            if let Some(frame) = codec.decode(&buffer[..length]).unwrap().pop() {
+                break ProtocolMessage::from_bytes(&frame).unwrap();
+            }
+        };
+
+        // A pushed command goes to its handler while the response still reaches the pending request
+        let notice = ProtocolMessage::event(42, b"maintenance".to_vec());
+        let response = ProtocolMessage::response_to(&request_message, b"done".to_vec());
+        for message in [notice, response] {
+            server_stream.write_all(&codec.encode(&message.to_bytes()).unwrap()).await.unwrap();
+        }
+
+        assert_eq!(handled_receiver.recv().await.unwrap(), b"maintenance".to_vec());
+        assert_eq!(request.await.unwrap().unwrap().get_payload(), b"done");
+    }
+
+    fn websocket_config(port: u16, message_type: WebSocketMessageType) -> ClientSocketConfig {
+        let websocket_config = WebSocketConfig::new("/socket").with_outgoing_message_type(message_type);
+        ClientSocketConfig::new("127.0.0.1", port).with_transport(Transport::WebSocket(websocket_config))
+    }
+
+    // The client handshake only finishes once the server answers, so both sides run together
+    async fn accept_websocket(service: &ClientSocketServiceImpl, listener: &TcpListener) -> WebSocketStream<TcpStream> {
+        let (connect_result, websocket_stream) = tokio::join!(service.connect(), async {
+            tokio_tungstenite::accept_async(listener.accept().await.unwrap().0).await.unwrap()
+        });
+        connect_result.unwrap();
+        websocket_stream
+    }
+
+    fn start_socket_workers(service: &ClientSocketServiceImpl) {
+        for name in [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME] {
+            service.thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_socket_workers_over_websocket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(websocket_config(port, WebSocketMessageType::Binary));
+
+        let mut channels = service.register_socket_workers().unwrap();
+        let mut websocket_stream = accept_websocket(&service, &listener).await;
+        assert_eq!(service.get_stream().unwrap_err(), ClientSocketError::UnsupportedTransport("raw stream access needs the TCP transport".to_string()));
+        start_socket_workers(&service);
+
+        // Each message is one frame, no length header
+        channels.send(b"echo".to_vec()).await.unwrap();
+        assert_eq!(websocket_stream.next().await.unwrap().unwrap(), Message::binary(b"echo".to_vec()));
+
+        // The receiver answers pings on its own and hands text messages over as bytes
+        websocket_stream.send(Message::Ping(b"beat".to_vec().into())).await.unwrap();
+        assert_eq!(websocket_stream.next().await.unwrap().unwrap(), Message::Pong(b"beat".to_vec().into()));
+        websocket_stream.send(Message::text("hello")).await.unwrap();
+        assert_eq!(channels.receive().await.unwrap(), b"hello".to_vec());
+
+        // A server side close is answered and counts as a lost link
+        websocket_stream.close(None).await.unwrap();
+        assert!(matches!(websocket_stream.next().await, Some(Ok(Message::Close(_)))));
+        drop(websocket_stream);
+
+        let mut state_receiver = service.repository.lock().unwrap().subscribe_state();
+        state_receiver.wait_for(|state| *state == ClientSocketState::Reconnecting).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_websocket_text_messages_and_client_close() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(websocket_config(port, WebSocketMessageType::Text));
+
+        let channels = service.register_socket_workers().unwrap();
+        let mut websocket_stream = accept_websocket(&service, &listener).await;
+        start_socket_workers(&service);
+
+        let (transmit_sender, _receive_receiver) = channels.into_parts();
+        // Invalid UTF-8 is dropped without closing the connection
+        transmit_sender.send(vec![0xff, 0xfe]).await.unwrap();
+        transmit_sender.send(b"status".to_vec()).await.unwrap();
+        assert_eq!(websocket_stream.next().await.unwrap().unwrap(), Message::text("status"));
+
+        // Dropping every sender makes the transmitter start the close handshake
+        drop(transmit_sender);
+        assert!(matches!(websocket_stream.next().await, Some(Ok(Message::Close(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_stops_socket_workers_with_a_close_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(websocket_config(port, WebSocketMessageType::Binary));
+
+        let mut channels = service.register_socket_workers().unwrap();
+        let mut websocket_stream = accept_websocket(&service, &listener).await;
+        start_socket_workers(&service);
+
+        // One round trip each way so both workers hold their half of the socket
+        channels.send(b"ping".to_vec()).await.unwrap();
+        assert_eq!(websocket_stream.next().await.unwrap().unwrap(), Message::binary(b"ping".to_vec()));
+        websocket_stream.send(Message::binary(b"pong".to_vec())).await.unwrap();
+        assert_eq!(channels.receive().await.unwrap(), b"pong".to_vec());
+
+        service.disconnect().unwrap();
+        assert!(matches!(websocket_stream.next().await, Some(Ok(Message::Close(_)))));
+
+        for name in [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME] {
+            let worker_handle = service.thread_worker_repository.lock().unwrap().find_worker_handle(name).unwrap();
+            assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        }
+        assert_eq!(service.get_state().unwrap(), ClientSocketState::Disconnected);
+    }
+
+    #[tokio::test]
+    async fn test_receiver_dispatches_commands_over_websocket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(websocket_config(port, WebSocketMessageType::Binary));
+
+        let (handled_sender, mut handled_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let notice_handler = move |message: ProtocolMessage| -> CommandHandlerFuture {
+            let handled_sender = handled_sender.clone();
+            Box::pin(async move {
+                handled_sender.send(message.into_payload()).unwrap();
+            })
+        };
+        service.register_command_handler(42, Arc::new(notice_handler)).unwrap();
+
+        let (request_client, _unsolicited_receiver) = service.register_request_client().unwrap();
+        let mut websocket_stream = accept_websocket(&service, &listener).await;
+        start_socket_workers(&service);
+
+        let request = tokio::spawn(async move { request_client.request(ProtocolMessage::request(7, Vec::new())).await });
+        let request_message = ProtocolMessage::from_bytes(&websocket_stream.next().await.unwrap().unwrap().into_data()).unwrap();
+
+        let notice = ProtocolMessage::event(42, b"maintenance".to_vec());
+        let response = ProtocolMessage::response_to(&request_message, b"done".to_vec());
+        for message in [notice, response] {
+            websocket_stream.send(Message::binary(message.to_bytes())).await.unwrap();
+        }
+
+        assert_eq!(handled_receiver.recv().await.unwrap(), b"maintenance".to_vec());
+        assert_eq!(request.await.unwrap().unwrap().get_payload(), b"done");
+    }
+
+    // Answers heartbeat pings until `answer_pings` is false, in which case it only swallows them
+    async fn serve_heartbeats(mut server_stream: TcpStream, answer_pings: bool) {
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default());
+        let mut buffer = vec![0u8; 256];
+
+        loop {
+            let length = match server_stream.read(&mut buffer).await {
+                Ok(0) | Err(_) => return,
+                Ok(length) => length,
+            };
+
+            for frame in codec.decode(&buffer[..length]).unwrap() {
+                let ping = ProtocolMessage::from_bytes(&frame).unwrap();
+                assert_eq!(ping.get_command_code(), DEFAULT_HEARTBEAT_COMMAND_CODE);
+
+                if answer_pings {
+                    let pong = ProtocolMessage::response_to(&ping, Vec::new());
+                    if server_stream.write_all(&codec.encode(&pong.to_bytes()).unwrap()).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_reconnects_after_missed_pongs() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let service = create_service(ClientSocketConfig::new("127.0.0.1", port));
+
+        let (request_client, _unsolicited_receiver) = service.register_request_client().unwrap();
+        let heartbeat_config = HeartbeatConfig::new()
+            .with_interval(Duration::from_millis(20))
+            .with_pong_timeout(Duration::from_millis(30))
+            .with_max_missed_pongs(2);
+        service.register_heartbeat_worker(&request_client, heartbeat_config).unwrap();
+
+        service.connect().await.unwrap();
+        let (silent_server_stream, _) = listener.accept().await.unwrap();
+        // The first server reads pings but never answers, like a hung process
+        tokio::spawn(serve_heartbeats(silent_server_stream, false));
+
+        let reconnector = service.start_auto_reconnect(ReconnectPolicy::new().with_backoff(Duration::from_millis(10), Duration::from_millis(50), 2.0));
+        for name in [RECEIVER_WORKER_NAME, TRANSMITTER_WORKER_NAME, HEARTBEAT_WORKER_NAME] {
+            service.thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+
+        let (answering_server_stream, _) = listener.accept().await.unwrap();
+        tokio::spawn(serve_heartbeats(answering_server_stream, true));
+
+        let mut state_receiver = service.repository.lock().unwrap().subscribe_state();
+        state_receiver.wait_for(ClientSocketState::is_connected).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while service.get_heartbeat_status().unwrap().get_last_round_trip_time().is_none() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.expect("the heartbeat never got a pong after reconnecting");
+        let heartbeat_status = service.get_heartbeat_status().unwrap();
+        assert_eq!(heartbeat_status.get_consecutive_missed_pongs(), 0);
+        assert_eq!(heartbeat_status.get_total_missed_pongs(), 2);
+
+        service.thread_worker_repository.lock().unwrap().begin_shutdown();
+        reconnector.await.unwrap();
+    }
+}
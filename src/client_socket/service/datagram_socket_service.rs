@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use crate::client_socket::entity::client_socket_channels::ClientSocketChannels;
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+#[async_trait]
+pub trait DatagramSocketServiceTrait {
+    async fn connect(&self) -> Result<(), ClientSocketError>;
+    fn disconnect(&self) -> Result<(), ClientSocketError>;
+    fn is_connected(&self) -> Result<bool, ClientSocketError>;
+    fn register_datagram_workers(&self) -> Result<ClientSocketChannels, ClientSocketError>;
+    fn get_loss_statistics(&self) -> Result<DatagramLossStatistics, ClientSocketError>;
+    fn resync_sequence_numbers(&self) -> Result<(), ClientSocketError>;
+}
@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use crate::client_socket::connector::datagram_connector::DatagramConnector;
+use crate::client_socket::entity::client_socket_channels::ClientSocketChannels;
+use crate::client_socket::entity::datagram_loss_statistics::DatagramLossStatistics;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::datagram_socket_repository::DatagramSocketRepositoryTrait;
+use crate::client_socket::repository::datagram_socket_repository_impl::DatagramSocketRepositoryImpl;
+use crate::client_socket::service::datagram_socket_service::DatagramSocketServiceTrait;
+use crate::client_socket::worker::datagram_receiver_worker_factory::DatagramReceiverWorkerFactory;
+use crate::client_socket::worker::datagram_transmitter_worker_factory::DatagramTransmitterWorkerFactory;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub struct DatagramSocketServiceImpl {
+    repository: Arc<Mutex<DatagramSocketRepositoryImpl>>,
+    thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+}
+
+impl DatagramSocketServiceImpl {
+    pub fn new(
+        repository: Arc<Mutex<DatagramSocketRepositoryImpl>>,
+        thread_worker_repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    ) -> Self {
+        DatagramSocketServiceImpl { repository, thread_worker_repository }
+    }
+
+    pub fn get_instance() -> Arc<Mutex<DatagramSocketServiceImpl>> {
+        lazy_static! {
+            static ref INSTANCE: Arc<Mutex<DatagramSocketServiceImpl>> =
+                Arc::new(Mutex::new(DatagramSocketServiceImpl::new(
+                    DatagramSocketRepositoryImpl::get_instance(),
+                    ThreadWorkerRepositoryImpl::get_instance(),
+                )));
+        }
+        INSTANCE.clone()
+    }
+}
+
+#[async_trait]
+impl DatagramSocketServiceTrait for DatagramSocketServiceImpl {
+    async fn connect(&self) -> Result<(), ClientSocketError> {
+        let config = {
+            let repository = self.repository.lock()?;
+            if repository.get_socket().is_ok() {
+                return Err(ClientSocketError::AlreadyConnected(repository.get_config().address()));
+            }
+            repository.get_config()
+        };
+
+        let socket = DatagramConnector::connect(&config).await?;
+        self.repository.lock()?.save_socket(socket);
+        Ok(())
+    }
+
+    fn disconnect(&self) -> Result<(), ClientSocketError> {
+        // Workers still holding the socket keep it open until they are stopped
+        self.repository.lock()?.take_socket();
+        Ok(())
+    }
+
+    fn is_connected(&self) -> Result<bool, ClientSocketError> {
+        Ok(self.repository.lock()?.get_socket().is_ok())
+    }
+
+    fn register_datagram_workers(&self) -> Result<ClientSocketChannels, ClientSocketError> {
+        let receive_receiver = DatagramReceiverWorkerFactory::new(Arc::clone(&self.repository)).register(&self.thread_worker_repository)?;
+        let transmit_sender = DatagramTransmitterWorkerFactory::new(Arc::clone(&self.repository)).register(&self.thread_worker_repository)?;
+
+        let max_message_size = self.repository.lock()?.get_config().get_max_payload_size() as u64;
+
+        Ok(ClientSocketChannels::new(transmit_sender, receive_receiver).with_max_message_size(max_message_size))
+    }
+
+    fn get_loss_statistics(&self) -> Result<DatagramLossStatistics, ClientSocketError> {
+        Ok(self.repository.lock()?.get_loss_statistics())
+    }
+
+    // For a peer known to have restarted, so its next datagram becomes the new baseline
+    fn resync_sequence_numbers(&self) -> Result<(), ClientSocketError> {
+        self.repository.lock()?.resync_sequence_numbers();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UdpSocket;
+    use crate::client_socket::codec::sequence_number_codec::SequenceNumberCodec;
+    use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+    use crate::client_socket::worker::datagram_receiver_worker_factory::DATAGRAM_RECEIVER_WORKER_NAME;
+    use crate::client_socket::worker::datagram_transmitter_worker_factory::DATAGRAM_TRANSMITTER_WORKER_NAME;
+    use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+
+    fn create_service(config: DatagramSocketConfig) -> DatagramSocketServiceImpl {
+        DatagramSocketServiceImpl::new(
+            Arc::new(Mutex::new(DatagramSocketRepositoryImpl::new(config))),
+            Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new())),
+        )
+    }
+
+    fn start_datagram_workers(service: &DatagramSocketServiceImpl) {
+        for name in [DATAGRAM_RECEIVER_WORKER_NAME, DATAGRAM_TRANSMITTER_WORKER_NAME] {
+            service.thread_worker_repository.lock().unwrap().start_thread_worker(name).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_and_disconnect() {
+        let service = create_service(DatagramSocketConfig::new("127.0.0.1", 9).with_bind_address("127.0.0.1:0"));
+
+        service.connect().await.unwrap();
+        assert!(service.is_connected().unwrap());
+        assert!(matches!(service.connect().await, Err(ClientSocketError::AlreadyConnected(_))));
+
+        service.disconnect().unwrap();
+        assert!(!service.is_connected().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_datagram_workers_echo() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server_socket.local_addr().unwrap().port();
+        let service = create_service(DatagramSocketConfig::new("127.0.0.1", port).with_bind_address("127.0.0.1:0"));
+
+        let mut channels = service.register_datagram_workers().unwrap();
+        service.connect().await.unwrap();
+        start_datagram_workers(&service);
+
+        channels.send(b"temperature=21".to_vec()).await.unwrap();
+
+        let mut buffer = [0u8; 64];
+        let (length, client_address) = server_socket.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..length], b"temperature=21");
+
+        server_socket.send_to(b"ack", client_address).await.unwrap();
+        assert_eq!(channels.receive().await.unwrap(), b"ack".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_sequence_numbers_detect_loss() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server_socket.local_addr().unwrap().port();
+        let config = DatagramSocketConfig::new("127.0.0.1", port).with_bind_address("127.0.0.1:0").with_sequence_numbers(true);
+        let service = create_service(config);
+
+        let mut channels = service.register_datagram_workers().unwrap();
+        service.connect().await.unwrap();
+        start_datagram_workers(&service);
+
+        channels.send(b"hello".to_vec()).await.unwrap();
+        let mut buffer = [0u8; 64];
+        let (length, client_address) = server_socket.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(SequenceNumberCodec::decode(&buffer[..length]).unwrap(), (0, &b"hello"[..]));
+
+        // Replies 2 and 3 never make it, 1 shows up after 4
+        for sequence_number in [0, 1, 4, 1] {
+            let datagram = SequenceNumberCodec::encode(sequence_number, format!("reply-{}", sequence_number).as_bytes());
+            server_socket.send_to(&datagram, client_address).await.unwrap();
+        }
+        // Too short to carry a sequence number, so it is dropped
+        server_socket.send_to(b"bad", client_address).await.unwrap();
+        server_socket.send_to(&SequenceNumberCodec::encode(5, b"reply-5"), client_address).await.unwrap();
+
+        for expected in ["reply-0", "reply-1", "reply-4", "reply-1", "reply-5"] {
+            assert_eq!(channels.receive().await.unwrap(), expected.as_bytes().to_vec());
+        }
+        assert_eq!(service.get_loss_statistics().unwrap(), DatagramLossStatistics::new(5, 2, 1));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod client_socket_service;
+pub mod client_socket_service_impl;
+pub mod datagram_socket_service;
+pub mod datagram_socket_service_impl;
\ No newline at end of file
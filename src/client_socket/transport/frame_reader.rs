@@ -0,0 +1,57 @@
+use futures_util::stream::SplitStream;
+use futures_util::StreamExt;
+use tokio::io::AsyncReadExt;
+use tokio_tungstenite::tungstenite::{Error as WebSocketError, Message};
+use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+use crate::client_socket::entity::socket_connection::ClientWebSocketStream;
+use crate::client_socket::entity::socket_stream::SocketReadHalf;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+// Read side of a connection, yielding whole frames whatever the transport
+#[derive(Debug)]
+pub enum FrameReader {
+    Stream { read_half: SocketReadHalf, codec: LengthPrefixedCodec },
+    WebSocket(SplitStream<ClientWebSocketStream>),
+}
+
+impl FrameReader {
+    pub fn is_websocket(&self) -> bool {
+        matches!(self, FrameReader::WebSocket(_))
+    }
+
+    // Returns the frames completed by the next read, or None once the server closed the connection.
+    // `buffer` is only used by the stream transport.
+    pub async fn read_frames(&mut self, buffer: &mut [u8]) -> Result<Option<Vec<Vec<u8>>>, ClientSocketError> {
+        match self {
+            FrameReader::Stream { read_half, codec } => {
+                // Reports a bad header left behind by the previous read before waiting for more bytes
+                let frame_list = codec.decode(&[])?;
+                if !frame_list.is_empty() {
+                    return Ok(Some(frame_list));
+                }
+
+                let length = read_half.read(buffer).await.map_err(|error| ClientSocketError::Io(error.to_string()))?;
+                if length == 0 {
+                    return Ok(None);
+                }
+
+                codec.decode(&buffer[..length]).map(Some)
+            }
+            FrameReader::WebSocket(websocket_stream) => loop {
+                let message = match websocket_stream.next().await {
+                    Some(Ok(message)) => message,
+                    None | Some(Err(WebSocketError::ConnectionClosed)) => return Ok(None),
+                    Some(Err(error)) => return Err(ClientSocketError::WebSocket(error.to_string())),
+                };
+
+                match message {
+                    Message::Binary(payload) => return Ok(Some(vec![payload.to_vec()])),
+                    Message::Text(text) => return Ok(Some(vec![text.as_bytes().to_vec()])),
+                    // tungstenite queues the close reply and pong answers; they go out on the next read,
+                    // which after a close ends with ConnectionClosed
+                    Message::Close(_) | Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
+                }
+            },
+        }
+    }
+}
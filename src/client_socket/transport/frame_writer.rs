@@ -0,0 +1,77 @@
+use futures_util::stream::SplitSink;
+use futures_util::SinkExt;
+use tokio::io::AsyncWriteExt;
+use tokio_tungstenite::tungstenite::Message;
+use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+use crate::client_socket::entity::socket_connection::ClientWebSocketStream;
+use crate::client_socket::entity::socket_stream::SocketWriteHalf;
+use crate::client_socket::entity::websocket_message_type::WebSocketMessageType;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+
+// Write side of a connection, sending one frame per call whatever the transport
+#[derive(Debug)]
+pub enum FrameWriter {
+    Stream {
+        write_half: SocketWriteHalf,
+        codec: LengthPrefixedCodec,
+    },
+    WebSocket {
+        websocket_sink: SplitSink<ClientWebSocketStream, Message>,
+        message_type: WebSocketMessageType,
+        max_frame_size: usize,
+    },
+}
+
+impl FrameWriter {
+    pub fn is_websocket(&self) -> bool {
+        matches!(self, FrameWriter::WebSocket { .. })
+    }
+
+    // FrameTooLarge and InvalidMessage only reject this payload; any other error means the connection is gone
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<(), ClientSocketError> {
+        match self {
+            FrameWriter::Stream { write_half, codec } => {
+                let frame = codec.encode(payload)?;
+                write_half.write_all(&frame).await.map_err(|error| ClientSocketError::Io(error.to_string()))
+            }
+            FrameWriter::WebSocket { websocket_sink, message_type, max_frame_size } => {
+                if payload.len() > *max_frame_size {
+                    return Err(ClientSocketError::FrameTooLarge { size: payload.len() as u64, max_size: *max_frame_size as u64 });
+                }
+
+                let message = match message_type {
+                    WebSocketMessageType::Binary => Message::binary(payload.to_vec()),
+                    WebSocketMessageType::Text => {
+                        let text = String::from_utf8(payload.to_vec())
+                            .map_err(|_| ClientSocketError::InvalidMessage("text WebSocket message is not UTF-8".to_string()))?;
+                        Message::text(text)
+                    }
+                };
+
+                websocket_sink.send(message).await.map_err(|error| ClientSocketError::WebSocket(error.to_string()))
+            }
+        }
+    }
+
+    pub async fn send_ping(&mut self, payload: &[u8]) -> Result<(), ClientSocketError> {
+        match self {
+            FrameWriter::Stream { .. } => Err(ClientSocketError::UnsupportedTransport("ping frames need the WebSocket transport".to_string())),
+            FrameWriter::WebSocket { websocket_sink, .. } => websocket_sink
+                .send(Message::Ping(payload.to_vec().into()))
+                .await
+                .map_err(|error| ClientSocketError::WebSocket(error.to_string())),
+        }
+    }
+
+    // Ends our side cleanly: a TCP FIN for streams, the close handshake for WebSockets
+    pub async fn close(&mut self) {
+        match self {
+            FrameWriter::Stream { write_half, .. } => {
+                let _ = write_half.shutdown().await;
+            }
+            FrameWriter::WebSocket { websocket_sink, .. } => {
+                let _ = websocket_sink.close().await;
+            }
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod frame_reader;
+pub mod frame_writer;
\ No newline at end of file
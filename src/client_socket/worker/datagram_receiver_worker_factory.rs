@@ -0,0 +1,121 @@
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use crate::client_socket::codec::sequence_number_codec::SequenceNumberCodec;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::entity::sequence_observation::SequenceObservation;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::datagram_socket_repository::DatagramSocketRepositoryTrait;
+use crate::client_socket::repository::datagram_socket_repository_impl::DatagramSocketRepositoryImpl;
+use crate::client_socket::worker::socket_worker_support::{fail_without_socket, forward_message, register_receiver_worker};
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub const DATAGRAM_RECEIVER_WORKER_NAME: &str = "DatagramReceiver";
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+
+pub struct DatagramReceiverWorkerFactory {
+    datagram_socket_repository: Arc<Mutex<DatagramSocketRepositoryImpl>>,
+    channel_capacity: usize,
+}
+
+impl DatagramReceiverWorkerFactory {
+    pub fn new(datagram_socket_repository: Arc<Mutex<DatagramSocketRepositoryImpl>>) -> Self {
+        DatagramReceiverWorkerFactory {
+            datagram_socket_repository,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    pub fn with_channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    // Registers the receiver under DATAGRAM_RECEIVER_WORKER_NAME and returns where reply payloads arrive
+    pub fn register(&self, thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Result<mpsc::Receiver<Vec<u8>>, ClientSocketError> {
+        register_receiver_worker(thread_worker_repository, DATAGRAM_RECEIVER_WORKER_NAME, self.channel_capacity, |message_sender| self.create(message_sender))
+    }
+
+    pub fn create(&self, message_sender: mpsc::Sender<Vec<u8>>) -> ThreadWorkerContextFunction {
+        let datagram_socket_repository = Arc::clone(&self.datagram_socket_repository);
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let datagram_socket_repository = Arc::clone(&datagram_socket_repository);
+            let message_sender = message_sender.clone();
+
+            Box::pin(async move {
+                let socket = datagram_socket_repository.lock()
+                    .map_err(ClientSocketError::from)
+                    .and_then(|repository| Ok((repository.get_socket()?, repository.get_config())));
+
+                match socket {
+                    Ok((socket, config)) => {
+                        receive_loop(&socket, &config, message_sender, &context, &datagram_socket_repository).await;
+                    }
+                    Err(error) => fail_without_socket(&context, "receive on", error),
+                }
+            })
+        })
+    }
+}
+
+async fn receive_loop(
+    socket: &UdpSocket,
+    config: &DatagramSocketConfig,
+    message_sender: mpsc::Sender<Vec<u8>>,
+    context: &ThreadWorkerContext,
+    datagram_socket_repository: &Arc<Mutex<DatagramSocketRepositoryImpl>>,
+) {
+    let mut buffer = vec![0u8; config.get_max_datagram_size()];
+
+    loop {
+        let receive_result = tokio::select! {
+            _ = context.cancelled() => return,
+            receive_result = socket.recv(&mut buffer) => receive_result,
+        };
+
+        let datagram = match receive_result {
+            Ok(length) => &buffer[..length],
+            // An ICMP port unreachable for an earlier send; the peer may come up later
+            Err(error) if error.kind() == ErrorKind::ConnectionRefused => continue,
+            Err(error) => {
+                eprintln!("{} failed to receive: {}", context.name(), error);
+                return;
+            }
+        };
+
+        let payload = if config.uses_sequence_numbers() {
+            let (sequence_number, payload) = match SequenceNumberCodec::decode(datagram) {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    eprintln!("{} dropped a datagram: {}", context.name(), error);
+                    continue;
+                }
+            };
+
+            let observation = datagram_socket_repository.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .observe_sequence_number(sequence_number);
+            match observation {
+                SequenceObservation::Gap { missing_count } => {
+                    eprintln!("{} lost {} datagram(s) before #{}", context.name(), missing_count, sequence_number);
+                }
+                SequenceObservation::Restarted => {
+                    eprintln!("{} saw the peer restart its numbering at #{}", context.name(), sequence_number);
+                }
+                SequenceObservation::InOrder | SequenceObservation::Late => {}
+            }
+            payload
+        } else {
+            datagram
+        };
+
+        if !forward_message(&message_sender, payload.to_vec()).await {
+            return;
+        }
+    }
+}
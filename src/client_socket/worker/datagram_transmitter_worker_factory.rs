@@ -0,0 +1,161 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use crate::client_socket::codec::sequence_number_codec::SequenceNumberCodec;
+use crate::client_socket::entity::datagram_socket_config::DatagramSocketConfig;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::datagram_socket_repository::DatagramSocketRepositoryTrait;
+use crate::client_socket::repository::datagram_socket_repository_impl::DatagramSocketRepositoryImpl;
+use crate::client_socket::worker::socket_worker_support::{fail_without_socket, register_transmitter_worker, share_message_receiver};
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub const DATAGRAM_TRANSMITTER_WORKER_NAME: &str = "DatagramTransmitter";
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+
+pub struct DatagramTransmitterWorkerFactory {
+    datagram_socket_repository: Arc<Mutex<DatagramSocketRepositoryImpl>>,
+    channel_capacity: usize,
+}
+
+impl DatagramTransmitterWorkerFactory {
+    pub fn new(datagram_socket_repository: Arc<Mutex<DatagramSocketRepositoryImpl>>) -> Self {
+        DatagramTransmitterWorkerFactory {
+            datagram_socket_repository,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    pub fn with_channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    // Registers the transmitter under DATAGRAM_TRANSMITTER_WORKER_NAME and returns where to queue outgoing datagrams
+    pub fn register(&self, thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Result<mpsc::Sender<Vec<u8>>, ClientSocketError> {
+        register_transmitter_worker(thread_worker_repository, DATAGRAM_TRANSMITTER_WORKER_NAME, self.channel_capacity, |message_receiver| self.create(message_receiver))
+    }
+
+    pub fn create(&self, message_receiver: mpsc::Receiver<Vec<u8>>) -> ThreadWorkerContextFunction {
+        let datagram_socket_repository = Arc::clone(&self.datagram_socket_repository);
+        let message_receiver = share_message_receiver(message_receiver);
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let datagram_socket_repository = Arc::clone(&datagram_socket_repository);
+            let message_receiver = Arc::clone(&message_receiver);
+
+            Box::pin(async move {
+                let socket = datagram_socket_repository.lock()
+                    .map_err(ClientSocketError::from)
+                    .and_then(|repository| Ok((repository.get_socket()?, repository.get_config())));
+
+                match socket {
+                    Ok((socket, config)) => {
+                        let mut message_receiver = message_receiver.lock().await;
+                        transmit_loop(&socket, &config, &mut message_receiver, &context, &datagram_socket_repository).await;
+                    }
+                    Err(error) => fail_without_socket(&context, "send from", error),
+                }
+            })
+        })
+    }
+}
+
+async fn transmit_loop(
+    socket: &UdpSocket,
+    config: &DatagramSocketConfig,
+    message_receiver: &mut mpsc::Receiver<Vec<u8>>,
+    context: &ThreadWorkerContext,
+    datagram_socket_repository: &Arc<Mutex<DatagramSocketRepositoryImpl>>,
+) {
+    loop {
+        let message = tokio::select! {
+            _ = context.cancelled() => return,
+            message = message_receiver.recv() => message,
+        };
+
+        let Some(message) = message else {
+            return;
+        };
+
+        // Checked before numbering so an oversized message does not show up as loss on the other side
+        if message.len() > config.get_max_payload_size() {
+            eprintln!("{} dropped an outgoing datagram: {}", context.name(), ClientSocketError::FrameTooLarge {
+                size: message.len() as u64,
+                max_size: config.get_max_payload_size() as u64,
+            });
+            continue;
+        }
+
+        let datagram = if config.uses_sequence_numbers() {
+            let sequence_number = datagram_socket_repository.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .next_sequence_number();
+            SequenceNumberCodec::encode(sequence_number, &message)
+        } else {
+            message
+        };
+
+        // Fire and forget: a failed send loses this datagram only
+        if let Err(error) = socket.send(&datagram).await {
+            eprintln!("{} failed to send: {}", context.name(), error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+    use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+
+    #[tokio::test]
+    async fn test_transmitter_numbers_datagrams_and_drops_oversized() {
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server_socket.local_addr().unwrap().port();
+
+        let config = DatagramSocketConfig::new("127.0.0.1", port).with_max_datagram_size(16).with_sequence_numbers(true);
+        let client_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client_socket.connect(config.address()).await.unwrap();
+
+        let datagram_socket_repository = Arc::new(Mutex::new(DatagramSocketRepositoryImpl::new(config)));
+        datagram_socket_repository.lock().unwrap().save_socket(client_socket);
+
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let message_sender = DatagramTransmitterWorkerFactory::new(Arc::clone(&datagram_socket_repository))
+            .register(&thread_worker_repository)
+            .unwrap();
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(DATAGRAM_TRANSMITTER_WORKER_NAME).unwrap();
+
+        // Nine bytes plus the header do not fit in sixteen
+        for message in [&b"cpu=1"[..], b"too-large", b"cpu=2"] {
+            message_sender.send(message.to_vec()).await.unwrap();
+        }
+
+        let mut buffer = [0u8; 64];
+        for (expected_sequence_number, expected_payload) in [(0, &b"cpu=1"[..]), (1, b"cpu=2")] {
+            let length = server_socket.recv(&mut buffer).await.unwrap();
+            assert_eq!(SequenceNumberCodec::decode(&buffer[..length]).unwrap(), (expected_sequence_number, expected_payload));
+        }
+
+        drop(message_sender);
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_transmitter_fails_without_socket() {
+        let datagram_socket_repository = Arc::new(Mutex::new(DatagramSocketRepositoryImpl::new(DatagramSocketConfig::new("127.0.0.1", 9))));
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let _message_sender = DatagramTransmitterWorkerFactory::new(datagram_socket_repository)
+            .register(&thread_worker_repository)
+            .unwrap();
+
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(DATAGRAM_TRANSMITTER_WORKER_NAME).unwrap();
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Failed(format!("has no socket to send from: {}", ClientSocketError::NotConnected))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::watch;
+use tokio::time::{interval, timeout, Instant, MissedTickBehavior};
+use crate::client_socket::entity::client_socket_state::ClientSocketState;
+use crate::client_socket::entity::heartbeat_config::HeartbeatConfig;
+use crate::client_socket::entity::protocol_message::ProtocolMessage;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+use crate::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use crate::client_socket::request::request_client::RequestClient;
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub const HEARTBEAT_WORKER_NAME: &str = "ClientSocketHeartbeat";
+
+// Pings go out as requests and pongs come back as their responses, so the worker rides on the
+// request client and works over any transport. It outlives reconnects: while the link is down it
+// just waits for the next connection.
+pub struct HeartbeatWorkerFactory {
+    client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+    request_client: RequestClient,
+    heartbeat_config: HeartbeatConfig,
+}
+
+impl HeartbeatWorkerFactory {
+    pub fn new(
+        client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+        request_client: RequestClient,
+        heartbeat_config: HeartbeatConfig,
+    ) -> Self {
+        HeartbeatWorkerFactory { client_socket_repository, request_client, heartbeat_config }
+    }
+
+    pub fn register(&self, thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Result<(), ClientSocketError> {
+        thread_worker_repository.lock()?.save_thread_worker_with_context(HEARTBEAT_WORKER_NAME, self.create());
+        Ok(())
+    }
+
+    pub fn create(&self) -> ThreadWorkerContextFunction {
+        let client_socket_repository = Arc::clone(&self.client_socket_repository);
+        let request_client = self.request_client.clone();
+        let heartbeat_config = self.heartbeat_config.clone();
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let client_socket_repository = Arc::clone(&client_socket_repository);
+            let request_client = request_client.clone();
+            let heartbeat_config = heartbeat_config.clone();
+
+            Box::pin(async move {
+                let state_receiver = client_socket_repository.lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .subscribe_state();
+
+                heartbeat_loop(state_receiver, &request_client, &heartbeat_config, &context, &client_socket_repository).await;
+            })
+        })
+    }
+}
+
+async fn heartbeat_loop(
+    mut state_receiver: watch::Receiver<ClientSocketState>,
+    request_client: &RequestClient,
+    heartbeat_config: &HeartbeatConfig,
+    context: &ThreadWorkerContext,
+    client_socket_repository: &Arc<Mutex<ClientSocketRepositoryImpl>>,
+) {
+    let mut ping_interval = interval(heartbeat_config.get_interval());
+    ping_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = context.cancelled() => return,
+            _ = ping_interval.tick() => {}
+        }
+
+        if !state_receiver.borrow_and_update().is_connected() {
+            let connected = tokio::select! {
+                biased;
+                _ = context.cancelled() => return,
+                connected = state_receiver.wait_for(ClientSocketState::is_connected) => connected.is_ok(),
+            };
+            if !connected {
+                return;
+            }
+
+            // Give the new connection a full interval before the first ping
+            ping_interval.reset();
+            continue;
+        }
+
+        let ping = ProtocolMessage::request(heartbeat_config.get_command_code(), Vec::new());
+        let sent_at = Instant::now();
+        // The outer timeout also covers waiting for room in the transmit queue
+        let pong = tokio::select! {
+            biased;
+            _ = context.cancelled() => return,
+            pong = timeout(heartbeat_config.get_pong_timeout(), request_client.request_with_timeout(ping, heartbeat_config.get_pong_timeout())) => pong,
+        };
+
+        let mut repository = client_socket_repository.lock().unwrap_or_else(PoisonError::into_inner);
+        match pong {
+            Ok(Ok(_)) => repository.record_pong(sent_at.elapsed()),
+            Ok(Err(_)) | Err(_) => {
+                let missed_pong_count = repository.record_missed_pong();
+                if missed_pong_count >= heartbeat_config.get_max_missed_pongs() {
+                    eprintln!("{} missed {} pongs in a row, treating the server as dead", context.name(), missed_pong_count);
+                    repository.mark_connection_lost();
+                }
+            }
+        }
+    }
+}
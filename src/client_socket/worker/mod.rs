@@ -0,0 +1,6 @@
+pub mod datagram_receiver_worker_factory;
+pub mod datagram_transmitter_worker_factory;
+pub mod heartbeat_worker_factory;
+pub mod receiver_worker_factory;
+pub mod socket_worker_support;
+pub mod transmitter_worker_factory;
\ No newline at end of file
@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::mpsc;
+use crate::client_socket::dispatcher::command_dispatch_queue::CommandDispatchQueue;
+use crate::client_socket::dispatcher::command_dispatcher::CommandDispatcher;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+use crate::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use crate::client_socket::transport::frame_reader::FrameReader;
+use crate::client_socket::worker::socket_worker_support::{fail_without_socket, forward_message, register_receiver_worker};
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub const RECEIVER_WORKER_NAME: &str = "ClientSocketReceiver";
+
+const DEFAULT_READ_BUFFER_SIZE: usize = 4096;
+const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+const DEFAULT_DISPATCH_QUEUE_CAPACITY: usize = 64;
+
+pub struct ReceiverWorkerFactory {
+    client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+    read_buffer_size: usize,
+    channel_capacity: usize,
+}
+
+impl ReceiverWorkerFactory {
+    pub fn new(client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>) -> Self {
+        ReceiverWorkerFactory {
+            client_socket_repository,
+            read_buffer_size: DEFAULT_READ_BUFFER_SIZE,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    pub fn with_read_buffer_size(mut self, read_buffer_size: usize) -> Self {
+        self.read_buffer_size = read_buffer_size;
+        self
+    }
+
+    pub fn with_channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    // Registers the receiver under RECEIVER_WORKER_NAME and returns where its messages arrive
+    pub fn register(&self, thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Result<mpsc::Receiver<Vec<u8>>, ClientSocketError> {
+        register_receiver_worker(thread_worker_repository, RECEIVER_WORKER_NAME, self.channel_capacity, |message_sender| self.create(message_sender))
+    }
+
+    pub fn create(&self, message_sender: mpsc::Sender<Vec<u8>>) -> ThreadWorkerContextFunction {
+        let client_socket_repository = Arc::clone(&self.client_socket_repository);
+        let read_buffer_size = self.read_buffer_size;
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let client_socket_repository = Arc::clone(&client_socket_repository);
+            let message_sender = message_sender.clone();
+
+            Box::pin(async move {
+                let frame_reader = client_socket_repository.lock()
+                    .map_err(ClientSocketError::from)
+                    .and_then(|mut repository| Ok((repository.take_frame_reader()?, repository.get_command_dispatcher())));
+
+                match frame_reader {
+                    Ok((frame_reader, command_dispatcher)) => {
+                        receive_loop(frame_reader, &command_dispatcher, message_sender, &context, read_buffer_size, &client_socket_repository).await;
+                    }
+                    Err(error) => fail_without_socket(&context, "read from", error),
+                }
+            })
+        })
+    }
+}
+
+async fn receive_loop(
+    mut frame_reader: FrameReader,
+    command_dispatcher: &CommandDispatcher,
+    message_sender: mpsc::Sender<Vec<u8>>,
+    context: &ThreadWorkerContext,
+    read_buffer_size: usize,
+    client_socket_repository: &Arc<Mutex<ClientSocketRepositoryImpl>>,
+) {
+    let mut buffer = vec![0u8; read_buffer_size];
+    let dispatch_queue = CommandDispatchQueue::start(command_dispatcher.clone(), DEFAULT_DISPATCH_QUEUE_CAPACITY);
+
+    loop {
+        let read_result = tokio::select! {
+            _ = context.cancelled() => return,
+            read_result = frame_reader.read_frames(&mut buffer) => read_result,
+        };
+
+        let frame_list = match read_result {
+            Ok(Some(frame_list)) => frame_list,
+            Ok(None) => break,
+            Err(error) => {
+                // A stream cannot be resynchronised after a bad header, so any error is treated as a lost link
+                eprintln!("{} failed to read: {}", context.name(), error);
+                break;
+            }
+        };
+
+        for frame in frame_list {
+            let Some(frame) = dispatch_queue.dispatch_frame(frame).await else {
+                continue;
+            };
+
+            if !forward_message(&message_sender, frame).await {
+                return;
+            }
+        }
+    }
+
+    client_socket_repository.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .mark_connection_lost();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::{TcpListener, TcpStream};
+    use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+    use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+    use crate::client_socket::entity::client_socket_state::ClientSocketState;
+    use crate::client_socket::entity::frame_format::FrameFormat;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+    use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+
+    #[tokio::test]
+    async fn test_receiver_forwards_bytes_until_eof() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()))));
+        let client_stream = TcpStream::connect(address).await.unwrap();
+        client_socket_repository.lock().unwrap().save_stream(Box::new(client_stream));
+        let (mut server_stream, _) = listener.accept().await.unwrap();
+
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut message_receiver = ReceiverWorkerFactory::new(Arc::clone(&client_socket_repository))
+            .register(&thread_worker_repository)
+            .unwrap();
+
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(RECEIVER_WORKER_NAME).unwrap();
+
+        // Two frames in one write, the second split over two writes
+        let codec = LengthPrefixedCodec::new(FrameFormat::default());
+        let mut frames = codec.encode(b"ping").unwrap();
+        frames.extend(codec.encode(b"pong").unwrap());
+
+        server_stream.write_all(&frames[..10]).await.unwrap();
+        assert_eq!(message_receiver.recv().await.unwrap(), b"ping".to_vec());
+        server_stream.write_all(&frames[10..]).await.unwrap();
+        assert_eq!(message_receiver.recv().await.unwrap(), b"pong".to_vec());
+
+        drop(server_stream);
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        assert_eq!(client_socket_repository.lock().unwrap().get_state(), ClientSocketState::Reconnecting);
+    }
+
+    #[tokio::test]
+    async fn test_receiver_fails_without_socket() {
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::default())));
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let _message_receiver = ReceiverWorkerFactory::new(client_socket_repository)
+            .register(&thread_worker_repository)
+            .unwrap();
+
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(RECEIVER_WORKER_NAME).unwrap();
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Failed(format!("has no socket to read from: {}", ClientSocketError::NotConnected))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::thread_control::entity::thread_worker::ThreadWorkerContextFunction;
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+// Shared so a restarted transmitter picks up the messages queued for the previous run
+pub type SharedMessageReceiver = Arc<tokio::sync::Mutex<mpsc::Receiver<Vec<u8>>>>;
+
+pub fn share_message_receiver(message_receiver: mpsc::Receiver<Vec<u8>>) -> SharedMessageReceiver {
+    Arc::new(tokio::sync::Mutex::new(message_receiver))
+}
+
+// Registers a worker that reads from a socket and returns where the messages it reads arrive
+pub fn register_receiver_worker(
+    thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    name: &str,
+    channel_capacity: usize,
+    create: impl FnOnce(mpsc::Sender<Vec<u8>>) -> ThreadWorkerContextFunction,
+) -> Result<mpsc::Receiver<Vec<u8>>, ClientSocketError> {
+    let (message_sender, message_receiver) = mpsc::channel(channel_capacity);
+
+    thread_worker_repository.lock()?.save_thread_worker_with_context(name, create(message_sender));
+    Ok(message_receiver)
+}
+
+// Registers a worker that writes to a socket and returns where to queue the messages it writes
+pub fn register_transmitter_worker(
+    thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    name: &str,
+    channel_capacity: usize,
+    create: impl FnOnce(mpsc::Receiver<Vec<u8>>) -> ThreadWorkerContextFunction,
+) -> Result<mpsc::Sender<Vec<u8>>, ClientSocketError> {
+    let (message_sender, message_receiver) = mpsc::channel(channel_capacity);
+
+    thread_worker_repository.lock()?.save_thread_worker_with_context(name, create(message_receiver));
+    Ok(message_sender)
+}
+
+// Returns false once nobody listens any more, so there is no point in keeping the socket read
+pub async fn forward_message(message_sender: &mpsc::Sender<Vec<u8>>, message: Vec<u8>) -> bool {
+    message_sender.send(message).await.is_ok()
+}
+
+// Failing the run keeps a restart or reconnect race from leaving the link silently dead.
+// A worker stopped before it ever ran has nothing left to do, so that case ends quietly.
+pub fn fail_without_socket(context: &ThreadWorkerContext, action: &str, error: ClientSocketError) {
+    if !context.is_cancelled() {
+        context.fail(format!("has no socket to {}: {}", action, error));
+    }
+}
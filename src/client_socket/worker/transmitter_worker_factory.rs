@@ -0,0 +1,158 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::mpsc;
+use crate::client_socket::error::client_socket_error::ClientSocketError;
+use crate::client_socket::repository::client_socket_repository::ClientSocketRepositoryTrait;
+use crate::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use crate::client_socket::transport::frame_writer::FrameWriter;
+use crate::client_socket::worker::socket_worker_support::{fail_without_socket, register_transmitter_worker, share_message_receiver};
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+pub const TRANSMITTER_WORKER_NAME: &str = "ClientSocketTransmitter";
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+
+pub struct TransmitterWorkerFactory {
+    client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>,
+    channel_capacity: usize,
+}
+
+impl TransmitterWorkerFactory {
+    pub fn new(client_socket_repository: Arc<Mutex<ClientSocketRepositoryImpl>>) -> Self {
+        TransmitterWorkerFactory {
+            client_socket_repository,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    pub fn with_channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    // Registers the transmitter under TRANSMITTER_WORKER_NAME and returns where to queue outgoing messages
+    pub fn register(&self, thread_worker_repository: &Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Result<mpsc::Sender<Vec<u8>>, ClientSocketError> {
+        register_transmitter_worker(thread_worker_repository, TRANSMITTER_WORKER_NAME, self.channel_capacity, |message_receiver| self.create(message_receiver))
+    }
+
+    pub fn create(&self, message_receiver: mpsc::Receiver<Vec<u8>>) -> ThreadWorkerContextFunction {
+        let client_socket_repository = Arc::clone(&self.client_socket_repository);
+        let message_receiver = share_message_receiver(message_receiver);
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let client_socket_repository = Arc::clone(&client_socket_repository);
+            let message_receiver = Arc::clone(&message_receiver);
+
+            Box::pin(async move {
+                let frame_writer = client_socket_repository.lock()
+                    .map_err(ClientSocketError::from)
+                    .and_then(|mut repository| repository.take_frame_writer());
+
+                match frame_writer {
+                    Ok(frame_writer) => {
+                        let mut message_receiver = message_receiver.lock().await;
+                        transmit_loop(frame_writer, &mut message_receiver, &context, &client_socket_repository).await;
+                    }
+                    Err(error) => fail_without_socket(&context, "write to", error),
+                }
+            })
+        })
+    }
+}
+
+async fn transmit_loop(
+    mut frame_writer: FrameWriter,
+    message_receiver: &mut mpsc::Receiver<Vec<u8>>,
+    context: &ThreadWorkerContext,
+    client_socket_repository: &Arc<Mutex<ClientSocketRepositoryImpl>>,
+) {
+    loop {
+        let message = tokio::select! {
+            _ = context.cancelled() => {
+                // A deliberate stop still tells the server we are going, instead of just vanishing
+                frame_writer.close().await;
+                return;
+            }
+            message = message_receiver.recv() => message,
+        };
+
+        let Some(message) = message else {
+            // Every sender is gone: close our side so the server sees a clean close
+            frame_writer.close().await;
+            return;
+        };
+
+        match frame_writer.write_frame(&message).await {
+            Ok(()) => {}
+            Err(error @ (ClientSocketError::FrameTooLarge { .. } | ClientSocketError::InvalidMessage(_))) => {
+                // Only this message is unsendable; the connection itself is fine
+                eprintln!("{} dropped an outgoing message: {}", context.name(), error);
+            }
+            Err(error) => {
+                eprintln!("{} failed to write: {}", context.name(), error);
+                break;
+            }
+        }
+    }
+
+    client_socket_repository.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .mark_connection_lost();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+    use crate::client_socket::codec::length_prefixed_codec::LengthPrefixedCodec;
+    use crate::client_socket::entity::client_socket_config::ClientSocketConfig;
+    use crate::client_socket::entity::frame_format::FrameFormat;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+    use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+
+    #[tokio::test]
+    async fn test_transmitter_writes_queued_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::new("127.0.0.1", address.port()))));
+        let client_stream = TcpStream::connect(address).await.unwrap();
+        client_socket_repository.lock().unwrap().save_stream(Box::new(client_stream));
+        let (mut server_stream, _) = listener.accept().await.unwrap();
+
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let message_sender = TransmitterWorkerFactory::new(Arc::clone(&client_socket_repository))
+            .register(&thread_worker_repository)
+            .unwrap();
+
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(TRANSMITTER_WORKER_NAME).unwrap();
+
+        message_sender.send(b"hello ".to_vec()).await.unwrap();
+        message_sender.send(b"server".to_vec()).await.unwrap();
+        drop(message_sender);
+
+        let mut received = Vec::new();
+        server_stream.read_to_end(&mut received).await.unwrap();
+
+        let mut codec = LengthPrefixedCodec::new(FrameFormat::default());
+        assert_eq!(codec.decode(&received).unwrap(), vec![b"hello ".to_vec(), b"server".to_vec()]);
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_transmitter_fails_without_socket() {
+        let client_socket_repository = Arc::new(Mutex::new(ClientSocketRepositoryImpl::new(ClientSocketConfig::default())));
+        let thread_worker_repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let _message_sender = TransmitterWorkerFactory::new(client_socket_repository)
+            .register(&thread_worker_repository)
+            .unwrap();
+
+        let worker_handle = thread_worker_repository.lock().unwrap().start_thread_worker(TRANSMITTER_WORKER_NAME).unwrap();
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Failed(format!("has no socket to write to: {}", ClientSocketError::NotConnected))
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod client_socket;
+pub mod thread_control;
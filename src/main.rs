@@ -1,4 +1,3 @@
-mod thread_control;
 //
 // use tokio::time::{sleep, Duration};
 //
@@ -57,17 +56,25 @@ mod thread_control;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use lazy_static::lazy_static;
+use rust_tokio_client::client_socket::entity::reconnect_policy::ReconnectPolicy;
+use rust_tokio_client::client_socket::repository::client_socket_repository_impl::ClientSocketRepositoryImpl;
+use rust_tokio_client::client_socket::service::client_socket_service::ClientSocketServiceTrait;
+use rust_tokio_client::client_socket::service::client_socket_service_impl::ClientSocketServiceImpl;
+use rust_tokio_client::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+use rust_tokio_client::thread_control::shutdown::shutdown_coordinator::ShutdownCoordinator;
+
+type BServiceFunction = Arc<Mutex<Box<dyn Fn() + 'static + Send>>>;
 
 // A Repository trait 정의
 trait ARepository {
     fn a_repo_call(&self);
-    fn add_b_service_function(&mut self, name: &'static str, func: Arc<Mutex<Box<dyn Fn() + 'static + Send>>>);
+    fn add_b_service_function(&mut self, name: &'static str, func: BServiceFunction);
     fn execute_b_service_function(&self, name: &'static str);
 }
 
 // A Repository의 구현체
 struct ARepositoryImpl {
-    b_service_functions: HashMap<&'static str, Arc<Mutex<Box<dyn Fn() + 'static + Send>>>>,
+    b_service_functions: HashMap<&'static str, BServiceFunction>,
 }
 
 impl ARepositoryImpl {
@@ -83,7 +90,7 @@ impl ARepository for ARepositoryImpl {
         println!("A Repository Call");
     }
 
-    fn add_b_service_function(&mut self, name: &'static str, func: Arc<Mutex<Box<dyn Fn() + 'static + Send>>>) {
+    fn add_b_service_function(&mut self, name: &'static str, func: BServiceFunction) {
         self.b_service_functions.insert(name, func);
     }
 
@@ -134,8 +141,6 @@ lazy_static! {
     static ref B_SERVICE_INSTANCE: BServiceImpl = BServiceImpl;
 }
 
-struct TestStruct;
-
 // 구현하려는 함수
 fn execute_function(func: &Arc<Mutex<Box<dyn Fn() + Send>>>) {
     let guard = func.lock().unwrap(); // 여기서는 lock이 실패할 일이 없다고 가정합니다.
@@ -143,7 +148,7 @@ fn execute_function(func: &Arc<Mutex<Box<dyn Fn() + Send>>>) {
     closure(); // 클로저 호출
 }
 
-fn main() {
+fn run_b_service_function_demo() {
     let mut a_repo_instance = A_REPO_INSTANCE.lock().unwrap();
     a_repo_instance.add_b_service_function("b_service_function1", Arc::new(Mutex::new(Box::new(|| {
         B_SERVICE_INSTANCE.b_service_call();
@@ -169,3 +174,29 @@ fn main() {
     // // A Service 호출
     // A_SERVICE_INSTANCE.a_service_call(&mut *a_repo_instance);
 }
+
+#[tokio::main]
+async fn main() {
+    run_b_service_function_demo();
+
+    // CLIENT_SOCKET_HOST / CLIENT_SOCKET_PORT 로 지정된 서버에 연결
+    // 서비스 자체는 상태가 없으므로 singleton 저장소들로 바로 생성 (lock 을 잡은 채 await 하지 않기 위함)
+    let client_socket_service = ClientSocketServiceImpl::new(
+        ClientSocketRepositoryImpl::get_instance(),
+        ThreadWorkerRepositoryImpl::get_instance(),
+    );
+    if let Err(error) = client_socket_service.connect().await {
+        eprintln!("Client socket not connected: {}", error);
+    }
+    // 연결이 끊기면 backoff 를 두고 재연결 (종료 신호를 받으면 함께 멈춤)
+    let _reconnector = client_socket_service.start_auto_reconnect(ReconnectPolicy::default());
+
+    // Ctrl-C 또는 SIGTERM 수신 시 등록된 모든 thread worker 종료
+    let shutdown_coordinator = ShutdownCoordinator::new(ThreadWorkerRepositoryImpl::get_instance());
+
+    match shutdown_coordinator.shutdown_on_signal().await {
+        Ok(report) if report.is_clean() => println!("All thread workers stopped cleanly"),
+        Ok(report) => eprintln!("Thread workers did not exit cleanly: {:?}", report.unclean_worker_names()),
+        Err(error) => eprintln!("Failed to shut down thread workers: {}", error),
+    }
+}
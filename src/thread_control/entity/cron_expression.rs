@@ -0,0 +1,245 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+// Five years is enough for any valid expression, including one that only matches on Feb 29
+const SEARCH_LIMIT_DAYS: u64 = 366 * 5;
+
+// Standard five-field cron expression (minute hour day-of-month month day-of-week), evaluated in UTC
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronExpression {
+    pub fn parse(expression: &str) -> Result<Self, ThreadWorkerError> {
+        let normalized = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let field_list: Vec<&str> = normalized.split_whitespace().collect();
+        if field_list.len() != 5 {
+            return Err(ThreadWorkerError::InvalidSchedule(format!(
+                "Expected 5 cron fields but found {}: {}", field_list.len(), expression
+            )));
+        }
+
+        let mut days_of_week = parse_field(field_list[4], 0, 7, expression)?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(CronExpression {
+            expression: expression.to_string(),
+            minutes: parse_field(field_list[0], 0, 59, expression)?,
+            hours: parse_field(field_list[1], 0, 23, expression)?,
+            days_of_month: parse_field(field_list[2], 1, 31, expression)?,
+            months: parse_field(field_list[3], 1, 12, expression)?,
+            days_of_week,
+            // Like cron itself, a field starting with '*' (e.g. "*/2") does not count as a restriction
+            day_of_month_restricted: !field_list[2].starts_with('*'),
+            day_of_week_restricted: !field_list[4].starts_with('*'),
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    // First matching minute strictly after the given time
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        let elapsed_minutes = time.duration_since(UNIX_EPOCH).ok()?.as_secs() / 60;
+        let mut minute_index = elapsed_minutes + 1;
+        let last_day = minute_index / MINUTES_PER_DAY + SEARCH_LIMIT_DAYS;
+
+        while minute_index / MINUTES_PER_DAY <= last_day {
+            let day = minute_index / MINUTES_PER_DAY;
+
+            if self.matches_day(day) {
+                for minute_of_day in (minute_index % MINUTES_PER_DAY)..MINUTES_PER_DAY {
+                    if contains(self.hours, minute_of_day / 60) && contains(self.minutes, minute_of_day % 60) {
+                        let matched_minute = day * MINUTES_PER_DAY + minute_of_day;
+                        return Some(UNIX_EPOCH + Duration::from_secs(matched_minute * 60));
+                    }
+                }
+            }
+
+            minute_index = (day + 1) * MINUTES_PER_DAY;
+        }
+
+        None
+    }
+
+    fn matches_day(&self, day: u64) -> bool {
+        let (_, month, day_of_month) = civil_from_days(day);
+        // 1970-01-01 was a Thursday
+        let day_of_week = (day + 4) % 7;
+
+        if !contains(self.months, month) {
+            return false;
+        }
+
+        let day_of_month_matches = contains(self.days_of_month, day_of_month);
+        let day_of_week_matches = contains(self.days_of_week, day_of_week);
+
+        // Like classic cron, a restricted day-of-month and day-of-week match if either one does;
+        // otherwise both must match, which for a plain "*" always holds
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month_matches || day_of_week_matches
+        } else {
+            day_of_month_matches && day_of_week_matches
+        }
+    }
+}
+
+fn contains(bit_set: u64, value: u64) -> bool {
+    value < 64 && bit_set & (1 << value) != 0
+}
+
+fn parse_field(field: &str, min: u64, max: u64, expression: &str) -> Result<u64, ThreadWorkerError> {
+    let invalid = || ThreadWorkerError::InvalidSchedule(format!("Invalid cron field '{}' in: {}", field, expression));
+    let parse_number = |text: &str| text.parse::<u64>().map_err(|_| invalid());
+
+    let mut bit_set = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, parse_number(step)?),
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (parse_number(start)?, parse_number(end)?),
+                // "5/15" means every 15 starting from 5 up to the field maximum
+                None if part.contains('/') => (parse_number(range)?, max),
+                None => {
+                    let value = parse_number(range)?;
+                    (value, value)
+                }
+            },
+        };
+
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            bit_set |= 1 << value;
+        }
+    }
+
+    Ok(bit_set)
+}
+
+// Howard Hinnant's days-to-civil conversion, returning (year, month, day)
+fn civil_from_days(days: u64) -> (i64, u64, u64) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u64;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u64;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    // 2024-01-01T00:00:00Z, a Monday
+    const NEW_YEAR_2024: u64 = 1_704_067_200;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(NEW_YEAR_2024 / 86_400), (2024, 1, 1));
+        assert_eq!(civil_from_days(NEW_YEAR_2024 / 86_400 + 59), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_every_fifteen_minutes() {
+        let cron = CronExpression::parse("*/15 * * * *").unwrap();
+
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024)), Some(at(NEW_YEAR_2024 + 15 * 60)));
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024 + 16 * 60)), Some(at(NEW_YEAR_2024 + 30 * 60)));
+    }
+
+    #[test]
+    fn test_weekday_working_hours() {
+        let cron = CronExpression::parse("30 9-17/4 * * 1-5").unwrap();
+
+        // Saturday 2024-01-06 rolls over to Monday 2024-01-08 09:30
+        let saturday = NEW_YEAR_2024 + 5 * 86_400;
+        let monday = NEW_YEAR_2024 + 7 * 86_400;
+        assert_eq!(cron.next_after(at(saturday)), Some(at(monday + 9 * 3600 + 30 * 60)));
+        assert_eq!(cron.next_after(at(monday + 10 * 3600)), Some(at(monday + 13 * 3600 + 30 * 60)));
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // The 15th of the month or any Sunday, at midnight
+        let cron = CronExpression::parse("0 0 15 * 7").unwrap();
+
+        // First Sunday of 2024 is January 7th
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024)), Some(at(NEW_YEAR_2024 + 6 * 86_400)));
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024 + 13 * 86_400)), Some(at(NEW_YEAR_2024 + 14 * 86_400)));
+    }
+
+    #[test]
+    fn test_stepped_day_of_month_with_day_of_week() {
+        // Odd days that are also Mondays, since a day-of-month field starting with '*' is not a restriction
+        let cron = CronExpression::parse("0 0 */2 * 1").unwrap();
+
+        // January 2024 Mondays are the 1st, 8th, 15th, 22nd and 29th
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024)), Some(at(NEW_YEAR_2024 + 14 * 86_400)));
+    }
+
+    #[test]
+    fn test_leap_day() {
+        let cron = CronExpression::parse("0 12 29 2 *").unwrap();
+        let leap_day_2028 = NEW_YEAR_2024 + (366 + 365 + 365 + 365 + 31 + 28) * 86_400;
+
+        assert_eq!(cron.next_after(at(NEW_YEAR_2024 + 60 * 86_400)), Some(at(leap_day_2028 + 12 * 3600)));
+    }
+
+    #[test]
+    fn test_macros() {
+        assert_eq!(
+            CronExpression::parse("@daily").unwrap().next_after(at(NEW_YEAR_2024)),
+            Some(at(NEW_YEAR_2024 + 86_400))
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in ["* * * *", "60 * * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *", "a * * * *"] {
+            assert!(matches!(
+                CronExpression::parse(expression),
+                Err(ThreadWorkerError::InvalidSchedule(_))
+            ), "{} should be rejected", expression);
+        }
+    }
+}
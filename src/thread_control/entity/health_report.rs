@@ -0,0 +1,51 @@
+use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+use crate::thread_control::entity::worker_health::WorkerHealth;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    worker_health_list: Vec<WorkerHealth>,
+}
+
+impl HealthReport {
+    pub fn new() -> Self {
+        HealthReport::default()
+    }
+
+    pub fn record(&mut self, worker_health: WorkerHealth) {
+        self.worker_health_list.push(worker_health);
+    }
+
+    pub fn get_worker_health_list(&self) -> &Vec<WorkerHealth> {
+        &self.worker_health_list
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&WorkerHealth> {
+        self.worker_health_list.iter().find(|worker_health| worker_health.name() == name)
+    }
+
+    // Workers that were started and are not ready, including ones that are stopping or failed
+    pub fn not_ready_worker_names(&self) -> Vec<&str> {
+        self.worker_health_list
+            .iter()
+            .filter(|worker_health| !matches!(worker_health.get_state(), ThreadWorkerState::Registered | ThreadWorkerState::Finished))
+            .filter(|worker_health| !worker_health.is_ready())
+            .map(WorkerHealth::name)
+            .collect()
+    }
+
+    pub fn not_live_worker_names(&self) -> Vec<&str> {
+        self.worker_health_list
+            .iter()
+            .filter(|worker_health| !worker_health.is_live())
+            .map(WorkerHealth::name)
+            .collect()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.not_ready_worker_names().is_empty()
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.not_live_worker_names().is_empty()
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::timeout;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+// Sending side of a worker's mailbox, handed out by the repository under the worker's name
+#[derive(Debug)]
+pub struct MailboxAddress<M> {
+    name: String,
+    sender: mpsc::Sender<M>,
+}
+
+impl<M: Send + 'static> MailboxAddress<M> {
+    pub fn new(name: &str, sender: mpsc::Sender<M>) -> Self {
+        MailboxAddress {
+            name: name.to_string(),
+            sender,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Waits for room when the mailbox is full
+    pub async fn send(&self, message: M) -> Result<(), ThreadWorkerError> {
+        self.sender
+            .send(message)
+            .await
+            .map_err(|_| ThreadWorkerError::MailboxClosed(self.name.clone()))
+    }
+
+    pub fn try_send(&self, message: M) -> Result<(), ThreadWorkerError> {
+        self.sender.try_send(message).map_err(|error| match error {
+            mpsc::error::TrySendError::Full(_) => ThreadWorkerError::MailboxFull(self.name.clone()),
+            mpsc::error::TrySendError::Closed(_) => ThreadWorkerError::MailboxClosed(self.name.clone()),
+        })
+    }
+
+    // The message carries the reply sender; a worker that drops it without answering closes the request
+    pub async fn request<R>(&self, build_message: impl FnOnce(oneshot::Sender<R>) -> M) -> Result<R, ThreadWorkerError> {
+        let (reply_sender, reply_receiver) = oneshot::channel();
+
+        self.send(build_message(reply_sender)).await?;
+
+        reply_receiver
+            .await
+            .map_err(|_| ThreadWorkerError::MailboxClosed(self.name.clone()))
+    }
+
+    // Gives up with Timeout once the time runs out, whether the wait was for room in the mailbox or for the answer
+    pub async fn request_with_timeout<R>(&self, build_message: impl FnOnce(oneshot::Sender<R>) -> M, request_timeout: Duration) -> Result<R, ThreadWorkerError> {
+        timeout(request_timeout, self.request(build_message))
+            .await
+            .map_err(|_| ThreadWorkerError::Timeout(self.name.clone()))?
+    }
+}
+
+impl<M> Clone for MailboxAddress<M> {
+    fn clone(&self) -> Self {
+        MailboxAddress {
+            name: self.name.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_try_send_and_request() {
+        let (sender, mut receiver) = mpsc::channel::<oneshot::Sender<u32>>(1);
+        let address = MailboxAddress::new("Counter", sender);
+
+        let (reply_sender, _reply_receiver) = oneshot::channel();
+        address.try_send(reply_sender).unwrap();
+
+        let (reply_sender, _reply_receiver) = oneshot::channel();
+        assert_eq!(address.try_send(reply_sender), Err(ThreadWorkerError::MailboxFull("Counter".to_string())));
+
+        receiver.recv().await.unwrap();
+
+        let responder = tokio::spawn(async move {
+            let reply_sender = receiver.recv().await.unwrap();
+            reply_sender.send(7).unwrap();
+        });
+
+        assert_eq!(address.request(|reply_sender| reply_sender).await, Ok(7));
+        responder.await.unwrap();
+
+        assert_eq!(
+            address.request(|reply_sender| reply_sender).await,
+            Err(ThreadWorkerError::MailboxClosed("Counter".to_string()))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_with_timeout() {
+        let (sender, mut receiver) = mpsc::channel::<oneshot::Sender<u32>>(1);
+        let address = MailboxAddress::new("Counter", sender);
+
+        assert_eq!(
+            address.request_with_timeout(|reply_sender| reply_sender, Duration::from_millis(100)).await,
+            Err(ThreadWorkerError::Timeout("Counter".to_string()))
+        );
+        assert!(receiver.try_recv().is_ok());
+    }
+}
@@ -0,0 +1,22 @@
+use tokio::time::MissedTickBehavior;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissedTickPolicy {
+    // Run every missed tick back to back until the schedule has caught up
+    Burst,
+    // Run once right away, drop the other missed ticks and carry on from the next tick on the original grid
+    #[default]
+    Skip,
+    // Run once right away and shift the rest of the schedule from there
+    Delay,
+}
+
+impl MissedTickPolicy {
+    pub fn to_missed_tick_behavior(self) -> MissedTickBehavior {
+        match self {
+            MissedTickPolicy::Burst => MissedTickBehavior::Burst,
+            MissedTickPolicy::Skip => MissedTickBehavior::Skip,
+            MissedTickPolicy::Delay => MissedTickBehavior::Delay,
+        }
+    }
+}
@@ -1 +1,21 @@
-pub mod thread_worker;
\ No newline at end of file
+pub mod cron_expression;
+pub mod health_report;
+pub mod mailbox_address;
+pub mod missed_tick_policy;
+pub mod restart_policy;
+pub mod supervision_policy;
+pub mod thread_worker;
+pub mod thread_worker_context;
+pub mod thread_worker_state;
+pub mod worker_exit_status;
+pub mod worker_failure;
+pub mod worker_group;
+pub mod worker_group_config;
+pub mod worker_group_kind;
+pub mod worker_handle;
+pub mod worker_health;
+pub mod worker_liveness;
+pub mod worker_mailbox;
+pub mod worker_output;
+pub mod worker_readiness;
+pub mod worker_schedule;
\ No newline at end of file
@@ -0,0 +1,38 @@
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    // Cancelled runs were stopped on purpose, so no policy brings them back
+    pub fn should_restart(&self, exit_status: &WorkerExitStatus) -> bool {
+        match (self, exit_status) {
+            (_, WorkerExitStatus::Cancelled) => false,
+            (RestartPolicy::Never, _) => false,
+            (RestartPolicy::OnFailure, WorkerExitStatus::Panicked(_) | WorkerExitStatus::Failed(_)) => true,
+            (RestartPolicy::OnFailure, WorkerExitStatus::Completed) => false,
+            (RestartPolicy::Always, _) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_restart() {
+        let panicked = WorkerExitStatus::Panicked("boom".to_string());
+
+        assert!(!RestartPolicy::Never.should_restart(&panicked));
+        assert!(RestartPolicy::OnFailure.should_restart(&panicked));
+        assert!(RestartPolicy::OnFailure.should_restart(&WorkerExitStatus::Failed("lost socket".to_string())));
+        assert!(!RestartPolicy::OnFailure.should_restart(&WorkerExitStatus::Completed));
+        assert!(RestartPolicy::Always.should_restart(&WorkerExitStatus::Completed));
+        assert!(!RestartPolicy::Always.should_restart(&WorkerExitStatus::Cancelled));
+    }
+}
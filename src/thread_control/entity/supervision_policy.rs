@@ -0,0 +1,109 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::thread_control::entity::restart_policy::RestartPolicy;
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+
+pub type EscalationHook = Arc<dyn Fn(&str, &WorkerExitStatus) + Send + Sync + 'static>;
+
+#[derive(Clone)]
+pub struct SupervisionPolicy {
+    restart_policy: RestartPolicy,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff_multiplier: u32,
+    max_restarts: usize,
+    restart_window: Duration,
+    escalation_hook: Option<EscalationHook>,
+}
+
+impl SupervisionPolicy {
+    pub fn new(restart_policy: RestartPolicy) -> Self {
+        SupervisionPolicy {
+            restart_policy,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            backoff_multiplier: 2,
+            max_restarts: 5,
+            restart_window: Duration::from_secs(60),
+            escalation_hook: None,
+        }
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration, backoff_multiplier: u32) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self.backoff_multiplier = backoff_multiplier.max(1);
+        self
+    }
+
+    pub fn with_max_restarts(mut self, max_restarts: usize, restart_window: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.restart_window = restart_window;
+        self
+    }
+
+    pub fn with_escalation_hook(mut self, escalation_hook: EscalationHook) -> Self {
+        self.escalation_hook = Some(escalation_hook);
+        self
+    }
+
+    pub fn restart_policy(&self) -> RestartPolicy {
+        self.restart_policy
+    }
+
+    pub fn max_restarts(&self) -> usize {
+        self.max_restarts
+    }
+
+    pub fn restart_window(&self) -> Duration {
+        self.restart_window
+    }
+
+    pub fn backoff_for(&self, restart_count: usize) -> Duration {
+        let exponent = u32::try_from(restart_count).unwrap_or(u32::MAX);
+        let factor = self.backoff_multiplier.saturating_pow(exponent);
+
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    pub fn escalate(&self, name: &str, exit_status: &WorkerExitStatus) {
+        if let Some(escalation_hook) = &self.escalation_hook {
+            escalation_hook(name, exit_status);
+        }
+    }
+}
+
+impl fmt::Debug for SupervisionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SupervisionPolicy")
+            .field("restart_policy", &self.restart_policy)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("backoff_multiplier", &self.backoff_multiplier)
+            .field("max_restarts", &self.max_restarts)
+            .field("restart_window", &self.restart_window)
+            .field("escalation_hook", &self.escalation_hook.as_ref().map(|_| "Fn(&str, &WorkerExitStatus)"))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff_is_capped() {
+        let policy = SupervisionPolicy::new(RestartPolicy::Always)
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1), 2);
+
+        assert_eq!(policy.backoff_for(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff_for(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff_for(100), Duration::from_secs(1));
+    }
+}
@@ -1,27 +1,91 @@
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio::time::timeout;
+use crate::thread_control::entity::supervision_policy::SupervisionPolicy;
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+use crate::thread_control::entity::worker_mailbox::WorkerMailbox;
+use crate::thread_control::entity::worker_output::WorkerOutput;
+use crate::thread_control::entity::worker_schedule::WorkerSchedule;
+
+pub type ThreadWorkerFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+pub type LocalThreadWorkerFuture = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+pub type ThreadWorkerFunction = Box<dyn Fn() -> ThreadWorkerFuture + Send + 'static>;
+pub type SharedThreadWorkerFunction = Arc<Mutex<ThreadWorkerFunction>>;
+pub type ThreadWorkerContextFunction = Box<dyn Fn(ThreadWorkerContext) -> ThreadWorkerFuture + Send + 'static>;
+pub type SharedThreadWorkerContextFunction = Arc<Mutex<ThreadWorkerContextFunction>>;
+// The function itself still has to be Send to reach its thread; only the future it builds may be !Send
+pub type LocalThreadWorkerFunction = Box<dyn Fn(ThreadWorkerContext) -> LocalThreadWorkerFuture + Send + 'static>;
+pub type SharedLocalThreadWorkerFunction = Arc<Mutex<LocalThreadWorkerFunction>>;
+// Runs on a blocking thread; long loops should check context.is_cancelled() since they cannot be aborted
+pub type BlockingThreadWorkerFunction = Box<dyn Fn(ThreadWorkerContext) + Send + Sync + 'static>;
+pub type ThreadWorkerOutputFunction<T> = Box<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = T> + Send>> + Send + 'static>;
 
 #[derive(Clone)]
 pub struct ThreadWorker {
     name: String,
-    will_be_execute_function: Option<Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send + 'static>>>>,
+    will_be_execute_function: Option<SharedThreadWorkerFunction>,
+    will_be_execute_context_function: Option<SharedThreadWorkerContextFunction>,
+    will_be_execute_local_function: Option<SharedLocalThreadWorkerFunction>,
+    state: Arc<RwLock<ThreadWorkerState>>,
+    supervision_policy: Option<SupervisionPolicy>,
+    output: WorkerOutput,
+    schedule: Option<WorkerSchedule>,
+    worker_group: Option<String>,
+    blocking: bool,
+    dependency_list: Vec<String>,
+    signals_readiness: bool,
+    liveness_timeout: Option<Duration>,
+    mailbox: Option<WorkerMailbox>,
 }
 
 impl ThreadWorker {
     pub fn new(
         name: &str,
-        will_be_execute_function: Option<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send + 'static>>,
+        will_be_execute_function: Option<ThreadWorkerFunction>,
     ) -> Self {
-        let arc_function = will_be_execute_function.map(|func| Arc::new(Mutex::new(func)));
+        ThreadWorker {
+            will_be_execute_function: will_be_execute_function.map(|func| Arc::new(Mutex::new(func))),
+            ..ThreadWorker::registered(name)
+        }
+    }
+
+    pub fn new_with_context(name: &str, will_be_execute_context_function: ThreadWorkerContextFunction) -> Self {
+        ThreadWorker {
+            will_be_execute_context_function: Some(Arc::new(Mutex::new(will_be_execute_context_function))),
+            ..ThreadWorker::registered(name)
+        }
+    }
 
+    pub fn new_local(name: &str, will_be_execute_local_function: LocalThreadWorkerFunction) -> Self {
+        ThreadWorker {
+            will_be_execute_local_function: Some(Arc::new(Mutex::new(will_be_execute_local_function))),
+            ..ThreadWorker::registered(name)
+        }
+    }
+
+    // Every field but the function starts out the same, whichever kind of function the worker runs
+    fn registered(name: &str) -> Self {
         ThreadWorker {
             name: name.to_string(),
-            will_be_execute_function: arc_function,
+            will_be_execute_function: None,
+            will_be_execute_context_function: None,
+            will_be_execute_local_function: None,
+            state: Arc::new(RwLock::new(ThreadWorkerState::Registered)),
+            supervision_policy: None,
+            output: WorkerOutput::new(),
+            schedule: None,
+            worker_group: None,
+            blocking: false,
+            dependency_list: Vec::new(),
+            signals_readiness: false,
+            liveness_timeout: None,
+            mailbox: None,
         }
     }
 
@@ -29,15 +93,162 @@ impl ThreadWorker {
         &self.name
     }
 
-    pub fn get_will_be_execute_function(
-        &self,
-    ) -> Option<Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>>> {
+    pub fn get_will_be_execute_function(&self) -> Option<SharedThreadWorkerFunction> {
         self.will_be_execute_function.clone()
     }
 
-    pub fn get_will_be_execute_function_ref(&self) -> Option<&Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>>> {
+    pub fn get_will_be_execute_function_ref(&self) -> Option<&SharedThreadWorkerFunction> {
         self.will_be_execute_function.as_ref()
     }
+
+    pub fn get_will_be_execute_context_function(&self) -> Option<SharedThreadWorkerContextFunction> {
+        self.will_be_execute_context_function.clone()
+    }
+
+    pub fn get_will_be_execute_local_function(&self) -> Option<SharedLocalThreadWorkerFunction> {
+        self.will_be_execute_local_function.clone()
+    }
+
+    pub fn has_function(&self) -> bool {
+        self.will_be_execute_function.is_some()
+            || self.will_be_execute_context_function.is_some()
+            || self.will_be_execute_local_function.is_some()
+    }
+
+    // Plain functions never see a cancellation token, so only aborting can stop them
+    pub fn watches_cancellation(&self) -> bool {
+        self.will_be_execute_function.is_none()
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.will_be_execute_local_function.is_some()
+    }
+
+    // Plain functions simply ignore the context they would have been given
+    pub async fn create_future(&self, context: ThreadWorkerContext) -> Option<ThreadWorkerFuture> {
+        if let Some(context_function) = &self.will_be_execute_context_function {
+            let guard = context_function.lock().await;
+            return Some((*guard)(context));
+        }
+
+        if let Some(function) = &self.will_be_execute_function {
+            let guard = function.lock().await;
+            return Some((*guard)());
+        }
+
+        None
+    }
+
+    pub async fn create_local_future(&self, context: ThreadWorkerContext) -> Option<LocalThreadWorkerFuture> {
+        if let Some(local_function) = &self.will_be_execute_local_function {
+            let guard = local_function.lock().await;
+            return Some((*guard)(context));
+        }
+
+        self.create_future(context)
+            .await
+            .map(|future| future as LocalThreadWorkerFuture)
+    }
+
+    // Lets a typed worker share the slot its wrapped function writes into
+    pub fn with_output(mut self, output: WorkerOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn get_output(&self) -> &WorkerOutput {
+        &self.output
+    }
+
+    pub fn get_output_type_name(&self) -> Option<&'static str> {
+        self.output.get_output_type_name()
+    }
+
+    pub fn with_schedule(mut self, schedule: WorkerSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn get_schedule(&self) -> Option<&WorkerSchedule> {
+        self.schedule.as_ref()
+    }
+
+    // Marks a worker whose function hands its work to a blocking thread
+    pub fn with_blocking(mut self) -> Self {
+        self.blocking = true;
+        self
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.blocking
+    }
+
+    pub fn get_worker_group(&self) -> Option<&str> {
+        self.worker_group.as_deref()
+    }
+
+    pub fn set_worker_group(&mut self, worker_group: &str) {
+        self.worker_group = Some(worker_group.to_string());
+    }
+
+    pub fn get_dependency_list(&self) -> &Vec<String> {
+        &self.dependency_list
+    }
+
+    pub fn add_dependency(&mut self, dependency_name: &str) {
+        if !self.dependency_list.iter().any(|name| name == dependency_name) {
+            self.dependency_list.push(dependency_name.to_string());
+        }
+    }
+
+    // Workers that signal readiness are only treated as started once they call context.mark_ready()
+    pub fn signals_readiness(&self) -> bool {
+        self.signals_readiness
+    }
+
+    pub fn set_signals_readiness(&mut self, signals_readiness: bool) {
+        self.signals_readiness = signals_readiness;
+    }
+
+    pub fn get_liveness_timeout(&self) -> Option<Duration> {
+        self.liveness_timeout
+    }
+
+    pub fn set_liveness_timeout(&mut self, liveness_timeout: Duration) {
+        self.liveness_timeout = Some(liveness_timeout);
+    }
+
+    pub fn get_mailbox(&self) -> Option<&WorkerMailbox> {
+        self.mailbox.as_ref()
+    }
+
+    pub fn set_mailbox(&mut self, mailbox: WorkerMailbox) {
+        self.mailbox = Some(mailbox);
+    }
+
+    pub fn get_supervision_policy(&self) -> Option<&SupervisionPolicy> {
+        self.supervision_policy.as_ref()
+    }
+
+    pub fn set_supervision_policy(&mut self, supervision_policy: SupervisionPolicy) {
+        self.supervision_policy = Some(supervision_policy);
+    }
+
+    pub fn state(&self) -> ThreadWorkerState {
+        *self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Clones share the same state, so a transition made from the worker thread is visible to the repository
+    pub fn transition_to(&self, next: ThreadWorkerState) -> bool {
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+
+        if !state.can_transition_to(next) {
+            return false;
+        }
+
+        *state = next;
+        true
+    }
 }
 
 impl AsRef<str> for ThreadWorker {
@@ -70,10 +281,33 @@ impl fmt::Debug for ThreadWorker {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ThreadWorker")
             .field("name", &self.name)
+            .field("state", &self.state())
+            .field("supervision_policy", &self.supervision_policy)
+            .field("schedule", &self.schedule)
+            .field("worker_group", &self.worker_group)
+            .field("blocking", &self.blocking)
+            .field("dependency_list", &self.dependency_list)
+            .field("signals_readiness", &self.signals_readiness)
+            .field("liveness_timeout", &self.liveness_timeout)
+            .field("mailbox", &self.mailbox)
             .field(
                 "will_be_execute_function",
                 &match &self.will_be_execute_function {
-                    Some(_) => "Some(Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>>)",
+                    Some(_) => "Some(Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>>>)",
+                    None => "None",
+                },
+            )
+            .field(
+                "will_be_execute_context_function",
+                &match &self.will_be_execute_context_function {
+                    Some(_) => "Some(Arc<Mutex<Box<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>>>)",
+                    None => "None",
+                },
+            )
+            .field(
+                "will_be_execute_local_function",
+                &match &self.will_be_execute_local_function {
+                    Some(_) => "Some(Arc<Mutex<Box<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = ()>>> + Send>>>)",
                     None => "None",
                 },
             )
@@ -99,6 +333,52 @@ mod tests {
         assert_eq!(worker.name(), "John Doe");
     }
 
+    #[tokio::test]
+    async fn test_worker_state_transition() {
+        let worker = ThreadWorker::new("John Doe", None);
+        let shared_worker = worker.clone();
+        assert_eq!(worker.state(), ThreadWorkerState::Registered);
+
+        assert!(worker.transition_to(ThreadWorkerState::Running));
+        assert_eq!(shared_worker.state(), ThreadWorkerState::Running);
+
+        assert!(!shared_worker.transition_to(ThreadWorkerState::Registered));
+        assert_eq!(worker.state(), ThreadWorkerState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_context_function() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use tokio_util::sync::CancellationToken;
+
+        let observed_cancel = Arc::new(AtomicBool::new(false));
+        let worker_observed_cancel = Arc::clone(&observed_cancel);
+
+        let context_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let observed_cancel = Arc::clone(&worker_observed_cancel);
+            Box::pin(async move {
+                assert_eq!(context.name(), "Context Worker");
+                context.cancelled().await;
+                observed_cancel.store(true, Ordering::SeqCst);
+            })
+        };
+
+        let worker = ThreadWorker::new_with_context("Context Worker", Box::new(context_function));
+        assert!(worker.has_function());
+
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+
+        let future = worker
+            .create_future(ThreadWorkerContext::new("Context Worker", cancellation_token))
+            .await
+            .unwrap();
+        future.await;
+
+        assert!(observed_cancel.load(Ordering::SeqCst));
+        assert!(!ThreadWorker::new("Idle Worker", None).has_function());
+    }
+
     #[tokio::test]
     async fn test_worker_as_ref() {
         let worker = ThreadWorker::new("John Doe", None);
@@ -108,7 +388,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_custom_function() {
-        let custom_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 println!("Custom function executed!");
             })
@@ -117,7 +397,7 @@ mod tests {
         let worker = ThreadWorker::new("John Doe", Some(Box::new(custom_function)));
         let found_function = worker.get_will_be_execute_function();
 
-        assert_eq!(found_function.is_some(), true);
+        assert!(found_function.is_some());
 
         if let Some(arc_function) = found_function {
             // Unwrap the Arc and lock the Mutex
@@ -127,7 +407,7 @@ mod tests {
             let function = &*guard;
 
             // Call the closure and execute the future
-            let future = (function as &dyn Fn() -> Pin<Box<dyn Future<Output = ()>>>)();
+            let future = (function as &dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>>)();
             future.await;
         } else {
             println!("No custom function found!");
@@ -136,7 +416,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_will_be_execute_function() {
-        let custom_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 println!("Custom function executed!");
             })
@@ -145,7 +425,7 @@ mod tests {
         let worker = ThreadWorker::new("John Doe", Some(Box::new(custom_function)));
         let found_function = worker.get_will_be_execute_function();
 
-        assert_eq!(found_function.is_some(), true);
+        assert!(found_function.is_some());
 
         if let Some(arc_function) = found_function {
             // Unwrap the Arc and lock the Mutex
@@ -165,7 +445,7 @@ mod tests {
     #[tokio::test]
     async fn test_my_sync_function() {
         // Use Box::pin(async {}) to wrap the synchronous function call in an asynchronous block
-        let custom_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 my_sync_function();
             })
@@ -190,7 +470,7 @@ mod tests {
     #[tokio::test]
     async fn test_my_async_function() {
         let custom_function = || {
-            Box::pin(my_async_function()) as Pin<Box<dyn Future<Output = ()>>>
+            Box::pin(my_async_function()) as Pin<Box<dyn Future<Output = ()> + Send>>
         };
 
         let worker = ThreadWorker::new("John Doe", Some(Box::new(custom_function)));
@@ -0,0 +1,93 @@
+use std::fmt::Display;
+use tokio_util::sync::CancellationToken;
+use crate::thread_control::entity::worker_failure::WorkerFailure;
+use crate::thread_control::entity::worker_liveness::WorkerLiveness;
+use crate::thread_control::entity::worker_mailbox::{MailboxReceiver, WorkerMailbox};
+use crate::thread_control::entity::worker_readiness::WorkerReadiness;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+#[derive(Clone, Debug)]
+pub struct ThreadWorkerContext {
+    name: String,
+    cancellation_token: CancellationToken,
+    readiness: WorkerReadiness,
+    liveness: WorkerLiveness,
+    failure: WorkerFailure,
+    mailbox: Option<WorkerMailbox>,
+}
+
+impl ThreadWorkerContext {
+    pub fn new(name: &str, cancellation_token: CancellationToken) -> Self {
+        ThreadWorkerContext {
+            name: name.to_string(),
+            cancellation_token,
+            readiness: WorkerReadiness::new(),
+            liveness: WorkerLiveness::new(),
+            failure: WorkerFailure::new(),
+            mailbox: None,
+        }
+    }
+
+    pub fn with_readiness(mut self, readiness: WorkerReadiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    pub fn with_liveness(mut self, liveness: WorkerLiveness) -> Self {
+        self.liveness = liveness;
+        self
+    }
+
+    pub fn with_failure(mut self, failure: WorkerFailure) -> Self {
+        self.failure = failure;
+        self
+    }
+
+    pub fn with_mailbox(mut self, mailbox: Option<WorkerMailbox>) -> Self {
+        self.mailbox = mailbox;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+
+    pub async fn cancelled(&self) {
+        self.cancellation_token.cancelled().await
+    }
+
+    // Lets workers that depend on this one start; only awaited for workers registered as signalling readiness
+    pub fn mark_ready(&self) {
+        self.readiness.mark_ready();
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.readiness.is_ready()
+    }
+
+    // Workers with a liveness timeout must call this more often than the timeout to be reported live
+    pub fn heartbeat(&self) {
+        self.liveness.beat();
+    }
+
+    // Ends this run as failed rather than completed once the worker's future returns
+    pub fn fail(&self, reason: impl Display) {
+        self.failure.record(reason.to_string());
+    }
+
+    // Receiving end of the mailbox attached to this worker in the repository
+    pub fn mailbox<M: Send + 'static>(&self) -> Result<MailboxReceiver<M>, ThreadWorkerError> {
+        self.mailbox
+            .as_ref()
+            .ok_or_else(|| ThreadWorkerError::NoMailbox(self.name.clone()))?
+            .receiver(&self.name)
+    }
+}
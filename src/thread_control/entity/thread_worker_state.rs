@@ -0,0 +1,70 @@
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThreadWorkerState {
+    Registered,
+    Running,
+    Stopping,
+    Finished,
+    Failed,
+}
+
+impl ThreadWorkerState {
+    pub fn can_transition_to(&self, next: ThreadWorkerState) -> bool {
+        use ThreadWorkerState::*;
+
+        matches!(
+            (self, next),
+            (Registered, Running)
+                | (Running, Stopping)
+                | (Running, Finished)
+                | (Running, Failed)
+                | (Stopping, Finished)
+                | (Stopping, Failed)
+                | (Finished, Running)
+                | (Failed, Running)
+        )
+    }
+
+    pub fn is_alive(&self) -> bool {
+        matches!(self, ThreadWorkerState::Running | ThreadWorkerState::Stopping)
+    }
+
+    pub fn from_exit_status(exit_status: &WorkerExitStatus) -> Self {
+        match exit_status {
+            WorkerExitStatus::Completed | WorkerExitStatus::Cancelled => ThreadWorkerState::Finished,
+            WorkerExitStatus::Panicked(_) | WorkerExitStatus::Failed(_) => ThreadWorkerState::Failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_transitions() {
+        assert!(ThreadWorkerState::Registered.can_transition_to(ThreadWorkerState::Running));
+        assert!(ThreadWorkerState::Running.can_transition_to(ThreadWorkerState::Stopping));
+        assert!(ThreadWorkerState::Stopping.can_transition_to(ThreadWorkerState::Finished));
+        assert!(ThreadWorkerState::Failed.can_transition_to(ThreadWorkerState::Running));
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        assert!(!ThreadWorkerState::Registered.can_transition_to(ThreadWorkerState::Finished));
+        assert!(!ThreadWorkerState::Running.can_transition_to(ThreadWorkerState::Running));
+        assert!(!ThreadWorkerState::Stopping.can_transition_to(ThreadWorkerState::Running));
+        assert!(!ThreadWorkerState::Finished.can_transition_to(ThreadWorkerState::Stopping));
+    }
+
+    #[test]
+    fn test_from_exit_status() {
+        assert_eq!(ThreadWorkerState::from_exit_status(&WorkerExitStatus::Completed), ThreadWorkerState::Finished);
+        assert_eq!(ThreadWorkerState::from_exit_status(&WorkerExitStatus::Cancelled), ThreadWorkerState::Finished);
+        assert_eq!(
+            ThreadWorkerState::from_exit_status(&WorkerExitStatus::Panicked("boom".to_string())),
+            ThreadWorkerState::Failed
+        );
+    }
+}
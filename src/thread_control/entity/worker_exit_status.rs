@@ -0,0 +1,84 @@
+use std::any::Any;
+use tokio::task::JoinError;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerExitStatus {
+    Completed,
+    Panicked(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl WorkerExitStatus {
+    pub fn from_panic_payload(payload: &(dyn Any + Send)) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unknown panic payload".to_string()
+        };
+
+        WorkerExitStatus::Panicked(message)
+    }
+
+    pub fn from_join_result(join_result: Result<WorkerExitStatus, JoinError>) -> Self {
+        match join_result {
+            Ok(exit_status) => exit_status,
+            Err(join_error) if join_error.is_panic() => {
+                WorkerExitStatus::from_panic_payload(join_error.into_panic().as_ref())
+            }
+            Err(_) => WorkerExitStatus::Cancelled,
+        }
+    }
+
+    pub fn into_result(self, name: &str) -> Result<(), ThreadWorkerError> {
+        match self {
+            WorkerExitStatus::Completed => Ok(()),
+            WorkerExitStatus::Panicked(message) => Err(ThreadWorkerError::Panicked { name: name.to_string(), message }),
+            WorkerExitStatus::Failed(message) => Err(ThreadWorkerError::Failed { name: name.to_string(), message }),
+            WorkerExitStatus::Cancelled => Err(ThreadWorkerError::Cancelled(name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_panic_payload() {
+        let payload: Box<dyn Any + Send> = Box::new("worker exploded");
+        assert_eq!(
+            WorkerExitStatus::from_panic_payload(payload.as_ref()),
+            WorkerExitStatus::Panicked("worker exploded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_string_panic_payload() {
+        let payload: Box<dyn Any + Send> = Box::new(format!("worker {} exploded", 7));
+        assert_eq!(
+            WorkerExitStatus::from_panic_payload(payload.as_ref()),
+            WorkerExitStatus::Panicked("worker 7 exploded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_into_result() {
+        assert_eq!(WorkerExitStatus::Completed.into_result("Worker"), Ok(()));
+        assert_eq!(
+            WorkerExitStatus::Panicked("boom".to_string()).into_result("Worker"),
+            Err(ThreadWorkerError::Panicked { name: "Worker".to_string(), message: "boom".to_string() })
+        );
+        assert_eq!(
+            WorkerExitStatus::Failed("lost socket".to_string()).into_result("Worker"),
+            Err(ThreadWorkerError::Failed { name: "Worker".to_string(), message: "lost socket".to_string() })
+        );
+        assert_eq!(
+            WorkerExitStatus::Cancelled.into_result("Worker"),
+            Err(ThreadWorkerError::Cancelled("Worker".to_string()))
+        );
+    }
+}
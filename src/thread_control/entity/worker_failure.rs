@@ -0,0 +1,42 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+// Shared by a worker's context and the repository; holds why the current run gave up, if it did
+#[derive(Clone, Debug, Default)]
+pub struct WorkerFailure {
+    message: Arc<Mutex<Option<String>>>,
+}
+
+impl WorkerFailure {
+    pub fn new() -> Self {
+        WorkerFailure::default()
+    }
+
+    // The first reason recorded wins, since later ones are usually fallout from it
+    pub fn record(&self, message: String) {
+        self.message
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert(message);
+    }
+
+    pub fn take(&self) -> Option<String> {
+        self.message.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_failure_wins() {
+        let failure = WorkerFailure::new();
+        let shared_failure = failure.clone();
+
+        shared_failure.record("lost socket".to_string());
+        shared_failure.record("write failed".to_string());
+
+        assert_eq!(failure.take(), Some("lost socket".to_string()));
+        assert_eq!(failure.take(), None);
+    }
+}
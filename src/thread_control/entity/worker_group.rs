@@ -0,0 +1,219 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::sync::mpsc;
+use tokio::task::LocalSet;
+use crate::thread_control::entity::thread_worker::LocalThreadWorkerFuture;
+use crate::thread_control::entity::worker_group_config::WorkerGroupConfig;
+use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+// Called on the pool thread, so the future it builds may be !Send
+pub type WorkerGroupJob = Box<dyn FnOnce() -> LocalThreadWorkerFuture + Send + 'static>;
+
+struct PoolThread {
+    job_sender: mpsc::UnboundedSender<(WorkerGroupJob, PoolThreadLease)>,
+    running_worker_count: Arc<AtomicUsize>,
+}
+
+enum WorkerGroupExecutor {
+    // Held in an Option so Drop can shut it down without blocking the caller's runtime
+    Runtime(Option<Runtime>),
+    ThreadPool(Vec<PoolThread>),
+}
+
+pub struct WorkerGroup {
+    config: WorkerGroupConfig,
+    executor: WorkerGroupExecutor,
+}
+
+impl WorkerGroup {
+    pub fn build(config: WorkerGroupConfig) -> Result<WorkerGroup, ThreadWorkerError> {
+        let executor = match config.get_kind() {
+            WorkerGroupKind::TokioRuntime => WorkerGroupExecutor::Runtime(Some(build_runtime(&config)?)),
+            WorkerGroupKind::ThreadPool => WorkerGroupExecutor::ThreadPool(build_thread_pool(&config)?),
+        };
+
+        Ok(WorkerGroup { config, executor })
+    }
+
+    pub fn get_config(&self) -> &WorkerGroupConfig {
+        &self.config
+    }
+
+    pub fn name(&self) -> &str {
+        self.config.get_name()
+    }
+
+    pub fn supports_local_workers(&self) -> bool {
+        matches!(self.executor, WorkerGroupExecutor::ThreadPool(_))
+    }
+
+    pub fn get_runtime_handle(&self) -> Option<Handle> {
+        match &self.executor {
+            WorkerGroupExecutor::Runtime(runtime) => runtime.as_ref().map(|runtime| runtime.handle().clone()),
+            WorkerGroupExecutor::ThreadPool(_) => None,
+        }
+    }
+
+    // Hands the job to the pool thread running the fewest jobs; it counts as busy until the job's future finishes
+    pub fn dispatch_job(&self, job: WorkerGroupJob) -> Result<(), ThreadWorkerError> {
+        let WorkerGroupExecutor::ThreadPool(pool_thread_list) = &self.executor else {
+            return Err(ThreadWorkerError::InvalidWorkerGroup(format!("{} is not a thread pool", self.name())));
+        };
+
+        let pool_thread = pool_thread_list
+            .iter()
+            .min_by_key(|pool_thread| pool_thread.running_worker_count.load(Ordering::SeqCst))
+            .ok_or_else(|| ThreadWorkerError::InvalidWorkerGroup(format!("{} has no threads", self.name())))?;
+
+        let lease = PoolThreadLease::new(Arc::clone(&pool_thread.running_worker_count));
+        pool_thread.job_sender
+            .send((job, lease))
+            .map_err(|_| ThreadWorkerError::SpawnFailed(format!("worker group {} has stopped", self.name())))
+    }
+}
+
+impl Drop for WorkerGroup {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks, which panics inside another runtime; the pool threads
+        // wind down by themselves once their job channels close
+        if let WorkerGroupExecutor::Runtime(runtime) = &mut self.executor {
+            if let Some(runtime) = runtime.take() {
+                runtime.shutdown_background();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for WorkerGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerGroup")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+struct PoolThreadLease {
+    running_worker_count: Arc<AtomicUsize>,
+}
+
+impl PoolThreadLease {
+    fn new(running_worker_count: Arc<AtomicUsize>) -> Self {
+        running_worker_count.fetch_add(1, Ordering::SeqCst);
+        PoolThreadLease { running_worker_count }
+    }
+}
+
+impl Drop for PoolThreadLease {
+    fn drop(&mut self) {
+        self.running_worker_count.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn build_runtime(config: &WorkerGroupConfig) -> Result<Runtime, ThreadWorkerError> {
+    let thread_index = AtomicUsize::new(0);
+    let thread_name_config = config.clone();
+
+    let mut builder = Builder::new_multi_thread();
+    builder
+        .worker_threads(config.get_thread_count())
+        .thread_name_fn(move || thread_name_config.thread_name(thread_index.fetch_add(1, Ordering::SeqCst)))
+        .enable_all();
+
+    if let Some(stack_size) = config.get_stack_size() {
+        builder.thread_stack_size(stack_size);
+    }
+
+    builder.build().map_err(|error| ThreadWorkerError::SpawnFailed(format!("worker group {}: {}", config.get_name(), error)))
+}
+
+fn build_thread_pool(config: &WorkerGroupConfig) -> Result<Vec<PoolThread>, ThreadWorkerError> {
+    (0..config.get_thread_count())
+        .map(|index| {
+            let (job_sender, job_receiver) = mpsc::unbounded_channel();
+
+            let mut thread_builder = thread::Builder::new().name(config.thread_name(index));
+            if let Some(stack_size) = config.get_stack_size() {
+                thread_builder = thread_builder.stack_size(stack_size);
+            }
+
+            thread_builder
+                .spawn(move || run_pool_thread(job_receiver))
+                .map_err(|error| ThreadWorkerError::SpawnFailed(format!("worker group {}: {}", config.get_name(), error)))?;
+
+            Ok(PoolThread { job_sender, running_worker_count: Arc::new(AtomicUsize::new(0)) })
+        })
+        .collect()
+}
+
+fn run_pool_thread(mut job_receiver: mpsc::UnboundedReceiver<(WorkerGroupJob, PoolThreadLease)>) {
+    let runtime = match Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            eprintln!("Worker group thread could not start its runtime: {}", error);
+            return;
+        }
+    };
+
+    let local_set = LocalSet::new();
+    local_set.block_on(&runtime, async move {
+        while let Some((job, lease)) = job_receiver.recv().await {
+            let future = job();
+            tokio::task::spawn_local(async move {
+                future.await;
+                drop(lease);
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_pool_names_and_balances_threads() {
+        let config = WorkerGroupConfig::new("Compute", WorkerGroupKind::ThreadPool)
+            .with_thread_count(2)
+            .with_stack_size(256 * 1024);
+        let worker_group = WorkerGroup::build(config).unwrap();
+        assert!(worker_group.supports_local_workers());
+        assert!(worker_group.get_runtime_handle().is_none());
+
+        let (name_sender, name_receiver) = std::sync::mpsc::channel();
+        let (release_sender, _) = tokio::sync::broadcast::channel::<()>(1);
+        for _ in 0..2 {
+            let name_sender = name_sender.clone();
+            let mut release_receiver = release_sender.subscribe();
+            let job: WorkerGroupJob = Box::new(move || {
+                name_sender.send(thread::current().name().map(str::to_string)).unwrap();
+                Box::pin(async move {
+                    let _ = release_receiver.recv().await;
+                })
+            });
+            worker_group.dispatch_job(job).unwrap();
+        }
+
+        // The first job keeps its thread busy, so the second lands on the other one
+        let mut thread_name_list: Vec<_> = (0..2).map(|_| name_receiver.recv().unwrap().unwrap()).collect();
+        thread_name_list.sort();
+        assert_eq!(thread_name_list, vec!["Compute-0".to_string(), "Compute-1".to_string()]);
+        let _ = release_sender.send(());
+    }
+
+    #[test]
+    fn test_tokio_runtime_group() {
+        let config = WorkerGroupConfig::new("Network", WorkerGroupKind::TokioRuntime).with_thread_name_prefix("net");
+        let worker_group = WorkerGroup::build(config).unwrap();
+        assert!(!worker_group.supports_local_workers());
+        assert!(matches!(worker_group.dispatch_job(Box::new(|| Box::pin(async {}))), Err(ThreadWorkerError::InvalidWorkerGroup(_))));
+
+        let thread_name = worker_group.get_runtime_handle().unwrap().block_on(async {
+            tokio::spawn(async { thread::current().name().map(str::to_string) }).await.unwrap()
+        });
+        assert_eq!(thread_name.as_deref(), Some("net-0"));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerGroupConfig {
+    name: String,
+    kind: WorkerGroupKind,
+    thread_count: usize,
+    thread_name_prefix: String,
+    stack_size: Option<usize>,
+}
+
+impl WorkerGroupConfig {
+    pub fn new(name: &str, kind: WorkerGroupKind) -> Self {
+        WorkerGroupConfig {
+            name: name.to_string(),
+            kind,
+            thread_count: 1,
+            thread_name_prefix: name.to_string(),
+            stack_size: None,
+        }
+    }
+
+    // A group always has at least one thread
+    pub fn with_thread_count(mut self, thread_count: usize) -> Self {
+        self.thread_count = thread_count.max(1);
+        self
+    }
+
+    // Threads are named "<prefix>-<index>"
+    pub fn with_thread_name_prefix(mut self, thread_name_prefix: &str) -> Self {
+        self.thread_name_prefix = thread_name_prefix.to_string();
+        self
+    }
+
+    pub fn with_stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_kind(&self) -> WorkerGroupKind {
+        self.kind
+    }
+
+    pub fn get_thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    pub fn get_thread_name_prefix(&self) -> &str {
+        &self.thread_name_prefix
+    }
+
+    pub fn get_stack_size(&self) -> Option<usize> {
+        self.stack_size
+    }
+
+    pub fn thread_name(&self, index: usize) -> String {
+        format!("{}-{}", self.thread_name_prefix, index)
+    }
+}
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerGroupKind {
+    // One multi-thread Tokio runtime shared by the group; only Send workers fit
+    TokioRuntime,
+    // OS threads that each drive a current-thread runtime, so local (!Send) workers fit as well.
+    // A worker stays on the thread it was placed on, the least busy one at start time.
+    ThreadPool,
+}
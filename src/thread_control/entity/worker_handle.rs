@@ -0,0 +1,231 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+use crate::thread_control::entity::worker_liveness::WorkerLiveness;
+use crate::thread_control::entity::worker_output::WorkerOutput;
+use crate::thread_control::entity::worker_readiness::WorkerReadiness;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+#[derive(Clone, Debug)]
+pub struct WorkerHandle {
+    name: String,
+    exit_receiver: watch::Receiver<Option<WorkerExitStatus>>,
+    abort_notify: Arc<Notify>,
+    cancellation_token: CancellationToken,
+    output: WorkerOutput,
+    readiness: WorkerReadiness,
+    liveness: WorkerLiveness,
+}
+
+impl WorkerHandle {
+    pub fn new(
+        name: &str,
+        exit_receiver: watch::Receiver<Option<WorkerExitStatus>>,
+        abort_notify: Arc<Notify>,
+        cancellation_token: CancellationToken,
+        output: WorkerOutput,
+    ) -> Self {
+        WorkerHandle {
+            name: name.to_string(),
+            exit_receiver,
+            abort_notify,
+            cancellation_token,
+            output,
+            readiness: WorkerReadiness::new(),
+            liveness: WorkerLiveness::new(),
+        }
+    }
+
+    pub fn with_readiness(mut self, readiness: WorkerReadiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    pub fn with_liveness(mut self, liveness: WorkerLiveness) -> Self {
+        self.liveness = liveness;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Asks the worker to stop through its context; only workers that watch their token will notice
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    pub fn abort(&self) {
+        self.abort_notify.notify_one();
+    }
+
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.exit_receiver.borrow().is_some()
+    }
+
+    pub fn exit_status(&self) -> Option<WorkerExitStatus> {
+        self.exit_receiver.borrow().clone()
+    }
+
+    pub async fn join(&self) -> WorkerExitStatus {
+        let mut exit_receiver = self.exit_receiver.clone();
+
+        let exit_status = match exit_receiver.wait_for(|exit_status| exit_status.is_some()).await {
+            Ok(exit_status) => exit_status.clone(),
+            Err(_) => None,
+        };
+
+        // The worker thread went away without reporting, which only happens if it died abnormally
+        exit_status.unwrap_or_else(|| {
+            WorkerExitStatus::Panicked(format!("Thread worker exited without status: {}", self.name))
+        })
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.readiness.is_ready()
+    }
+
+    pub fn since_last_heartbeat(&self) -> Duration {
+        self.liveness.since_last_heartbeat()
+    }
+
+    // A worker that exits before reporting ready only counts as ready if it completed
+    pub async fn wait_ready(&self) -> Result<(), ThreadWorkerError> {
+        tokio::select! {
+            biased;
+            _ = self.readiness.wait_ready() => Ok(()),
+            exit_status = self.join() => exit_status.into_result(&self.name),
+        }
+    }
+
+    pub async fn wait_ready_timeout(&self, duration: Duration) -> Result<(), ThreadWorkerError> {
+        timeout(duration, self.wait_ready())
+            .await
+            .map_err(|_| ThreadWorkerError::Timeout(self.name.clone()))?
+    }
+
+    pub fn take_output<T: 'static>(&self) -> Result<T, ThreadWorkerError> {
+        self.output.take(&self.name)
+    }
+
+    pub async fn join_output<T: 'static>(&self) -> Result<T, ThreadWorkerError> {
+        self.join().await.into_result(&self.name)?;
+        self.take_output()
+    }
+
+    pub async fn join_timeout(&self, duration: Duration) -> Result<WorkerExitStatus, ThreadWorkerError> {
+        timeout(duration, self.join())
+            .await
+            .map_err(|_| ThreadWorkerError::Timeout(self.name.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_join_returns_reported_status() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let handle = WorkerHandle::new("HandleWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new());
+
+        assert!(!handle.is_finished());
+        assert_eq!(handle.exit_status(), None);
+
+        exit_sender.send(Some(WorkerExitStatus::Completed)).unwrap();
+
+        assert_eq!(handle.join().await, WorkerExitStatus::Completed);
+        assert!(handle.is_finished());
+        assert_eq!(handle.name(), "HandleWorker");
+    }
+
+    #[tokio::test]
+    async fn test_join_without_status() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let handle = WorkerHandle::new("LostWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new());
+
+        drop(exit_sender);
+
+        assert!(matches!(handle.join().await, WorkerExitStatus::Panicked(_)));
+    }
+
+    #[tokio::test]
+    async fn test_join_timeout() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let handle = WorkerHandle::new("SlowWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new());
+
+        assert_eq!(
+            handle.join_timeout(Duration::from_millis(10)).await,
+            Err(ThreadWorkerError::Timeout("SlowWorker".to_string()))
+        );
+
+        exit_sender.send(Some(WorkerExitStatus::Cancelled)).unwrap();
+
+        assert_eq!(handle.join_timeout(Duration::from_millis(10)).await, Ok(WorkerExitStatus::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_join_output() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let output = WorkerOutput::new();
+        let handle = WorkerHandle::new("OutputWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), output.clone());
+
+        output.store("fetched".to_string());
+        exit_sender.send(Some(WorkerExitStatus::Completed)).unwrap();
+
+        assert_eq!(handle.join_output::<String>().await, Ok("fetched".to_string()));
+        assert_eq!(handle.take_output::<String>(), Err(ThreadWorkerError::NoOutput("OutputWorker".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_join_output_of_cancelled_worker() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let handle = WorkerHandle::new("CancelledWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new());
+
+        exit_sender.send(Some(WorkerExitStatus::Cancelled)).unwrap();
+
+        assert_eq!(
+            handle.join_output::<String>().await,
+            Err(ThreadWorkerError::Cancelled("CancelledWorker".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let readiness = WorkerReadiness::new();
+        let handle = WorkerHandle::new("ReadyWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new())
+            .with_readiness(readiness.clone());
+
+        assert_eq!(
+            handle.wait_ready_timeout(Duration::from_millis(10)).await,
+            Err(ThreadWorkerError::Timeout("ReadyWorker".to_string()))
+        );
+
+        readiness.mark_ready();
+        assert!(handle.is_ready());
+        assert_eq!(handle.wait_ready().await, Ok(()));
+
+        drop(exit_sender);
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready_of_worker_that_exited() {
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let handle = WorkerHandle::new("BrokenWorker", exit_receiver, Arc::new(Notify::new()), CancellationToken::new(), WorkerOutput::new());
+
+        exit_sender.send(Some(WorkerExitStatus::Panicked("boom".to_string()))).unwrap();
+
+        assert_eq!(
+            handle.wait_ready().await,
+            Err(ThreadWorkerError::Panicked { name: "BrokenWorker".to_string(), message: "boom".to_string() })
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerHealth {
+    name: String,
+    state: ThreadWorkerState,
+    ready: bool,
+    since_last_heartbeat: Option<Duration>,
+    liveness_timeout: Option<Duration>,
+}
+
+impl WorkerHealth {
+    pub fn new(name: &str, state: ThreadWorkerState) -> Self {
+        WorkerHealth {
+            name: name.to_string(),
+            state,
+            ready: false,
+            since_last_heartbeat: None,
+            liveness_timeout: None,
+        }
+    }
+
+    pub fn with_ready(mut self, ready: bool) -> Self {
+        self.ready = ready;
+        self
+    }
+
+    pub fn with_since_last_heartbeat(mut self, since_last_heartbeat: Duration) -> Self {
+        self.since_last_heartbeat = Some(since_last_heartbeat);
+        self
+    }
+
+    pub fn with_liveness_timeout(mut self, liveness_timeout: Option<Duration>) -> Self {
+        self.liveness_timeout = liveness_timeout;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_state(&self) -> ThreadWorkerState {
+        self.state
+    }
+
+    pub fn get_since_last_heartbeat(&self) -> Option<Duration> {
+        self.since_last_heartbeat
+    }
+
+    pub fn get_liveness_timeout(&self) -> Option<Duration> {
+        self.liveness_timeout
+    }
+
+    // Only a worker without a heartbeat deadline can never miss one
+    pub fn missed_heartbeat(&self) -> bool {
+        matches!(
+            (self.since_last_heartbeat, self.liveness_timeout),
+            (Some(since_last_heartbeat), Some(liveness_timeout)) if since_last_heartbeat > liveness_timeout
+        )
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.state == ThreadWorkerState::Running && self.ready
+    }
+
+    // Workers that were never started or have finished are not expected to beat
+    pub fn is_live(&self) -> bool {
+        match self.state {
+            ThreadWorkerState::Running | ThreadWorkerState::Stopping => !self.missed_heartbeat(),
+            ThreadWorkerState::Failed => false,
+            ThreadWorkerState::Registered | ThreadWorkerState::Finished => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missed_heartbeat() {
+        let health = WorkerHealth::new("Receiver", ThreadWorkerState::Running)
+            .with_ready(true)
+            .with_since_last_heartbeat(Duration::from_secs(3))
+            .with_liveness_timeout(Some(Duration::from_secs(2)));
+
+        assert!(health.missed_heartbeat());
+        assert!(!health.is_live());
+        assert!(health.is_ready());
+
+        let health = health.with_liveness_timeout(None);
+        assert!(health.is_live());
+        assert!(!WorkerHealth::new("Receiver", ThreadWorkerState::Failed).is_live());
+    }
+}
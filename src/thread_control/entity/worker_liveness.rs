@@ -0,0 +1,48 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// Shared by a worker's context and its handle; the start of a run counts as its first heartbeat
+#[derive(Clone, Debug)]
+pub struct WorkerLiveness {
+    last_heartbeat: Arc<Mutex<Instant>>,
+}
+
+impl WorkerLiveness {
+    pub fn new() -> Self {
+        WorkerLiveness {
+            last_heartbeat: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn beat(&self) {
+        *self.last_heartbeat.lock().unwrap_or_else(PoisonError::into_inner) = Instant::now();
+    }
+
+    pub fn since_last_heartbeat(&self) -> Duration {
+        self.last_heartbeat.lock().unwrap_or_else(PoisonError::into_inner).elapsed()
+    }
+}
+
+impl Default for WorkerLiveness {
+    fn default() -> Self {
+        WorkerLiveness::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_beat() {
+        let liveness = WorkerLiveness::new();
+        let worker_liveness = liveness.clone();
+
+        tokio::time::advance(Duration::from_secs(3)).await;
+        assert_eq!(liveness.since_last_heartbeat(), Duration::from_secs(3));
+
+        worker_liveness.beat();
+        assert_eq!(liveness.since_last_heartbeat(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,71 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use crate::thread_control::entity::mailbox_address::MailboxAddress;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+// Shared so a restarted worker picks up whatever was queued while it was down
+pub type MailboxReceiver<M> = Arc<Mutex<mpsc::Receiver<M>>>;
+
+// Type-erased mpsc channel owned by a worker; the message type is checked when either end is looked up.
+// The mailbox keeps a sender of its own, so a worker's recv only ends through cancellation.
+#[derive(Clone)]
+pub struct WorkerMailbox {
+    sender: Arc<dyn Any + Send + Sync>,
+    receiver: Arc<dyn Any + Send + Sync>,
+}
+
+impl WorkerMailbox {
+    pub fn new<M: Send + 'static>(capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<M>(capacity.max(1));
+        let receiver: MailboxReceiver<M> = Arc::new(Mutex::new(receiver));
+
+        WorkerMailbox {
+            sender: Arc::new(sender),
+            receiver: Arc::new(receiver),
+        }
+    }
+
+    pub fn address<M: Send + 'static>(&self, name: &str) -> Result<MailboxAddress<M>, ThreadWorkerError> {
+        let sender = self.sender
+            .downcast_ref::<mpsc::Sender<M>>()
+            .ok_or_else(|| ThreadWorkerError::MailboxTypeMismatch(name.to_string()))?;
+
+        Ok(MailboxAddress::new(name, sender.clone()))
+    }
+
+    pub fn receiver<M: Send + 'static>(&self, name: &str) -> Result<MailboxReceiver<M>, ThreadWorkerError> {
+        self.receiver
+            .downcast_ref::<MailboxReceiver<M>>()
+            .cloned()
+            .ok_or_else(|| ThreadWorkerError::MailboxTypeMismatch(name.to_string()))
+    }
+}
+
+impl fmt::Debug for WorkerMailbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerMailbox").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_typed_ends() {
+        let mailbox = WorkerMailbox::new::<String>(0);
+
+        assert_eq!(
+            mailbox.address::<u32>("Logger").unwrap_err(),
+            ThreadWorkerError::MailboxTypeMismatch("Logger".to_string())
+        );
+        assert!(mailbox.receiver::<u32>("Logger").is_err());
+
+        mailbox.address::<String>("Logger").unwrap().send("hello".to_string()).await.unwrap();
+
+        let receiver = mailbox.receiver::<String>("Logger").unwrap();
+        assert_eq!(receiver.lock().await.recv().await, Some("hello".to_string()));
+    }
+}
@@ -0,0 +1,103 @@
+use std::any::{type_name, Any, TypeId};
+use std::sync::{Arc, Mutex, PoisonError};
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+// Slot holding the value produced by the latest run of a worker. The value is stored type-erased,
+// but the type a worker was saved with is kept, so asking for another one fails even before the first run.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerOutput {
+    slot: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+    output_type: Option<(TypeId, &'static str)>,
+}
+
+impl WorkerOutput {
+    pub fn new() -> Self {
+        WorkerOutput::default()
+    }
+
+    pub fn for_type<T: Send + 'static>() -> Self {
+        WorkerOutput {
+            slot: Arc::default(),
+            output_type: Some((TypeId::of::<T>(), type_name::<T>())),
+        }
+    }
+
+    pub fn get_output_type_name(&self) -> Option<&'static str> {
+        self.output_type.map(|(_, output_type_name)| output_type_name)
+    }
+
+    pub fn store<T: Send + 'static>(&self, value: T) {
+        *self.slot.lock().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(value));
+    }
+
+    pub fn clear(&self) {
+        self.slot.lock().unwrap_or_else(PoisonError::into_inner).take();
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.slot.lock().unwrap_or_else(PoisonError::into_inner).is_some()
+    }
+
+    pub fn take<T: 'static>(&self, name: &str) -> Result<T, ThreadWorkerError> {
+        if self.output_type.is_some_and(|(type_id, _)| type_id != TypeId::of::<T>()) {
+            return Err(ThreadWorkerError::OutputTypeMismatch(name.to_string()));
+        }
+
+        let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+        let value = slot.take().ok_or_else(|| ThreadWorkerError::NoOutput(name.to_string()))?;
+
+        match value.downcast::<T>() {
+            Ok(value) => Ok(*value),
+            Err(value) => {
+                // Leave the value in place so a caller asking for the right type can still get it
+                *slot = Some(value);
+                Err(ThreadWorkerError::OutputTypeMismatch(name.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_take() {
+        let output = WorkerOutput::new();
+        assert!(!output.is_ready());
+
+        output.store(42_u32);
+        assert!(output.is_ready());
+
+        assert_eq!(
+            output.take::<String>("Fetcher"),
+            Err(ThreadWorkerError::OutputTypeMismatch("Fetcher".to_string()))
+        );
+        assert_eq!(output.take::<u32>("Fetcher"), Ok(42));
+        assert_eq!(output.take::<u32>("Fetcher"), Err(ThreadWorkerError::NoOutput("Fetcher".to_string())));
+    }
+
+    #[test]
+    fn test_clones_share_slot() {
+        let output = WorkerOutput::new();
+        let worker_output = output.clone();
+
+        worker_output.store(Ok::<&str, String>("payload"));
+        assert_eq!(output.take::<Result<&str, String>>("Fetcher"), Ok(Ok("payload")));
+
+        worker_output.store(1_i64);
+        output.clear();
+        assert!(!worker_output.is_ready());
+    }
+
+    #[test]
+    fn test_declared_type() {
+        let output = WorkerOutput::for_type::<u32>();
+        assert_eq!(output.get_output_type_name(), Some("u32"));
+        assert_eq!(WorkerOutput::new().get_output_type_name(), None);
+
+        // The wrong type is refused before anything has been stored
+        assert_eq!(output.take::<String>("Counter"), Err(ThreadWorkerError::OutputTypeMismatch("Counter".to_string())));
+        assert_eq!(output.take::<u32>("Counter"), Err(ThreadWorkerError::NoOutput("Counter".to_string())));
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+// Shared by a worker's context and its handle; flips once and stays set for that run
+#[derive(Clone, Debug)]
+pub struct WorkerReadiness {
+    ready_sender: Arc<watch::Sender<bool>>,
+}
+
+impl WorkerReadiness {
+    pub fn new() -> Self {
+        let (ready_sender, _) = watch::channel(false);
+
+        WorkerReadiness {
+            ready_sender: Arc::new(ready_sender),
+        }
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready_sender.send_replace(true);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self.ready_sender.borrow()
+    }
+
+    pub async fn wait_ready(&self) {
+        let mut ready_receiver = self.ready_sender.subscribe();
+
+        // The sender lives as long as self, so the wait can only end by becoming ready
+        let _ = ready_receiver.wait_for(|ready| *ready).await;
+    }
+}
+
+impl Default for WorkerReadiness {
+    fn default() -> Self {
+        WorkerReadiness::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_wait_ready() {
+        let readiness = WorkerReadiness::new();
+        let worker_readiness = readiness.clone();
+
+        assert!(!readiness.is_ready());
+        assert!(tokio::time::timeout(Duration::from_millis(10), readiness.wait_ready()).await.is_err());
+
+        worker_readiness.mark_ready();
+
+        assert!(readiness.is_ready());
+        readiness.wait_ready().await;
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime};
+use crate::thread_control::entity::cron_expression::CronExpression;
+use crate::thread_control::entity::missed_tick_policy::MissedTickPolicy;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleTrigger {
+    // Runs start on a fixed grid regardless of how long each run takes
+    FixedInterval(Duration),
+    // The wait starts only after the previous run has completed
+    FixedDelay(Duration),
+    OneShotAt(SystemTime),
+    Cron(CronExpression),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerSchedule {
+    trigger: ScheduleTrigger,
+    missed_tick_policy: MissedTickPolicy,
+}
+
+impl WorkerSchedule {
+    fn new(trigger: ScheduleTrigger) -> Self {
+        WorkerSchedule {
+            trigger,
+            missed_tick_policy: MissedTickPolicy::default(),
+        }
+    }
+
+    // A zero period would have the worker tick without ever yielding, so it is turned away here
+    pub fn fixed_interval(period: Duration) -> Result<Self, ThreadWorkerError> {
+        if period.is_zero() {
+            return Err(ThreadWorkerError::InvalidSchedule("fixed interval period must be greater than zero".to_string()));
+        }
+
+        Ok(WorkerSchedule::new(ScheduleTrigger::FixedInterval(period)))
+    }
+
+    pub fn fixed_delay(delay: Duration) -> Self {
+        WorkerSchedule::new(ScheduleTrigger::FixedDelay(delay))
+    }
+
+    pub fn one_shot_at(time: SystemTime) -> Self {
+        WorkerSchedule::new(ScheduleTrigger::OneShotAt(time))
+    }
+
+    pub fn cron(expression: &str) -> Result<Self, ThreadWorkerError> {
+        Ok(WorkerSchedule::new(ScheduleTrigger::Cron(CronExpression::parse(expression)?)))
+    }
+
+    pub fn with_missed_tick_policy(mut self, missed_tick_policy: MissedTickPolicy) -> Self {
+        self.missed_tick_policy = missed_tick_policy;
+        self
+    }
+
+    pub fn trigger(&self) -> &ScheduleTrigger {
+        &self.trigger
+    }
+
+    pub fn missed_tick_policy(&self) -> MissedTickPolicy {
+        self.missed_tick_policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_interval_rejects_zero_period() {
+        assert!(matches!(WorkerSchedule::fixed_interval(Duration::ZERO), Err(ThreadWorkerError::InvalidSchedule(_))));
+        assert_eq!(
+            WorkerSchedule::fixed_interval(Duration::from_millis(10)).unwrap().trigger(),
+            &ScheduleTrigger::FixedInterval(Duration::from_millis(10))
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod thread_worker_error;
\ No newline at end of file
@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::PoisonError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadWorkerError {
+    NotFound(String),
+    NoFunction(String),
+    AlreadyRunning(String),
+    LockPoisoned(String),
+    SpawnFailed(String),
+    RuntimeUnavailable(String),
+    Panicked { name: String, message: String },
+    Failed { name: String, message: String },
+    Cancelled(String),
+    Timeout(String),
+    NoOutput(String),
+    OutputTypeMismatch(String),
+    InvalidSchedule(String),
+    WorkerGroupNotFound(String),
+    InvalidWorkerGroup(String),
+    MissingDependency { name: String, dependency: String },
+    DependencyCycle(String),
+    NoMailbox(String),
+    MailboxTypeMismatch(String),
+    MailboxFull(String),
+    MailboxClosed(String),
+    SignalUnavailable(String),
+}
+
+impl fmt::Display for ThreadWorkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThreadWorkerError::NotFound(name) => write!(f, "Thread worker not found: {}", name),
+            ThreadWorkerError::NoFunction(name) => write!(f, "Thread worker function not found: {}", name),
+            ThreadWorkerError::AlreadyRunning(name) => write!(f, "Thread worker already running: {}", name),
+            ThreadWorkerError::LockPoisoned(message) => write!(f, "Thread worker lock poisoned: {}", message),
+            ThreadWorkerError::SpawnFailed(message) => write!(f, "Failed to spawn thread worker: {}", message),
+            ThreadWorkerError::RuntimeUnavailable(name) => write!(f, "No Tokio runtime to spawn thread worker on: {}", name),
+            ThreadWorkerError::Panicked { name, message } => write!(f, "Thread worker {} panicked: {}", name, message),
+            ThreadWorkerError::Failed { name, message } => write!(f, "Thread worker {} failed: {}", name, message),
+            ThreadWorkerError::Cancelled(name) => write!(f, "Thread worker cancelled: {}", name),
+            ThreadWorkerError::Timeout(name) => write!(f, "Timed out waiting for thread worker: {}", name),
+            ThreadWorkerError::NoOutput(name) => write!(f, "Thread worker has no output: {}", name),
+            ThreadWorkerError::OutputTypeMismatch(name) => write!(f, "Thread worker output has a different type: {}", name),
+            ThreadWorkerError::InvalidSchedule(message) => write!(f, "Invalid thread worker schedule: {}", message),
+            ThreadWorkerError::WorkerGroupNotFound(name) => write!(f, "Worker group not found: {}", name),
+            ThreadWorkerError::InvalidWorkerGroup(message) => write!(f, "Invalid worker group: {}", message),
+            ThreadWorkerError::MissingDependency { name, dependency } => write!(f, "Thread worker {} depends on unknown worker: {}", name, dependency),
+            ThreadWorkerError::DependencyCycle(names) => write!(f, "Thread worker dependency cycle among: {}", names),
+            ThreadWorkerError::NoMailbox(name) => write!(f, "Thread worker has no mailbox: {}", name),
+            ThreadWorkerError::MailboxTypeMismatch(name) => write!(f, "Thread worker mailbox has a different message type: {}", name),
+            ThreadWorkerError::MailboxFull(name) => write!(f, "Thread worker mailbox is full: {}", name),
+            ThreadWorkerError::MailboxClosed(name) => write!(f, "Thread worker mailbox is closed: {}", name),
+            ThreadWorkerError::SignalUnavailable(message) => write!(f, "Cannot listen for shutdown signals: {}", message),
+        }
+    }
+}
+
+impl Error for ThreadWorkerError {}
+
+impl<T> From<PoisonError<T>> for ThreadWorkerError {
+    fn from(error: PoisonError<T>) -> Self {
+        ThreadWorkerError::LockPoisoned(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ThreadWorkerError::NotFound("Receiver".to_string()).to_string(),
+            "Thread worker not found: Receiver"
+        );
+        assert_eq!(
+            ThreadWorkerError::Panicked { name: "Receiver".to_string(), message: "boom".to_string() }.to_string(),
+            "Thread worker Receiver panicked: boom"
+        );
+    }
+
+    #[test]
+    fn test_from_poison_error() {
+        let mutex = Arc::new(Mutex::new(()));
+        let poisoned_mutex = Arc::clone(&mutex);
+
+        let _ = thread::spawn(move || {
+            let _guard = poisoned_mutex.lock().unwrap();
+            panic!("Poison the lock");
+        }).join();
+
+        let error: ThreadWorkerError = mutex.lock().unwrap_err().into();
+        assert!(matches!(error, ThreadWorkerError::LockPoisoned(_)));
+    }
+}
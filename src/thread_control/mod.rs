@@ -1,3 +1,8 @@
 pub mod entity;
+pub mod error;
 pub mod repository;
-pub mod service;
\ No newline at end of file
+pub mod scheduler;
+pub mod service;
+pub mod shutdown;
+pub mod startup;
+pub mod supervisor;
\ No newline at end of file
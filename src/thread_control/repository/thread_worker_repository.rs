@@ -1,15 +1,55 @@
-use std::future::Future;
-use std::pin::Pin;
-use async_trait::async_trait;
-use crate::thread_control::entity::thread_worker::ThreadWorker;
+use std::time::Duration;
+use crate::thread_control::entity::health_report::HealthReport;
+use crate::thread_control::entity::supervision_policy::SupervisionPolicy;
+use crate::thread_control::entity::thread_worker::{BlockingThreadWorkerFunction, LocalThreadWorkerFunction, ThreadWorker, ThreadWorkerContextFunction, ThreadWorkerFunction, ThreadWorkerOutputFunction};
+use crate::thread_control::entity::worker_group_config::WorkerGroupConfig;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::entity::worker_schedule::WorkerSchedule;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
 
-#[async_trait]
 pub trait ThreadWorkerRepositoryTrait {
     fn save_thread_worker(
         &mut self,
         name: &str,
-        will_be_execute_function: Option<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send + 'static>>,
+        will_be_execute_function: Option<ThreadWorkerFunction>,
     );
+    fn save_thread_worker_with_context(
+        &mut self,
+        name: &str,
+        will_be_execute_context_function: ThreadWorkerContextFunction,
+    );
+    fn save_local_thread_worker(
+        &mut self,
+        name: &str,
+        will_be_execute_local_function: LocalThreadWorkerFunction,
+    );
+    fn save_blocking_thread_worker(
+        &mut self,
+        name: &str,
+        will_be_execute_blocking_function: BlockingThreadWorkerFunction,
+    );
+    fn save_scheduled_thread_worker(
+        &mut self,
+        name: &str,
+        schedule: WorkerSchedule,
+        will_be_execute_context_function: ThreadWorkerContextFunction,
+    );
+    fn save_thread_worker_with_output<T: Send + 'static>(
+        &mut self,
+        name: &str,
+        will_be_execute_function: ThreadWorkerOutputFunction<T>,
+    );
+    fn take_output<T: 'static>(&self, name: &str) -> Result<T, ThreadWorkerError>;
+    fn set_supervision_policy(&mut self, name: &str, supervision_policy: SupervisionPolicy) -> Result<(), ThreadWorkerError>;
+    fn save_worker_group(&mut self, worker_group_config: WorkerGroupConfig) -> Result<(), ThreadWorkerError>;
+    fn assign_worker_group(&mut self, name: &str, worker_group_name: &str) -> Result<(), ThreadWorkerError>;
+    fn add_dependency(&mut self, name: &str, dependency_name: &str) -> Result<(), ThreadWorkerError>;
+    fn require_readiness_signal(&mut self, name: &str) -> Result<(), ThreadWorkerError>;
+    fn set_liveness_timeout(&mut self, name: &str, liveness_timeout: Duration) -> Result<(), ThreadWorkerError>;
     fn find_by_name(&self, name: &str) -> Option<ThreadWorker>;
-    async fn start_thread_worker(&self, name: &str);
-}
\ No newline at end of file
+    fn find_worker_handle(&self, name: &str) -> Option<WorkerHandle>;
+    fn get_health_report(&self) -> HealthReport;
+    fn start_thread_worker(&mut self, name: &str) -> Result<WorkerHandle, ThreadWorkerError>;
+    fn stop_thread_worker(&mut self, name: &str) -> Result<(), ThreadWorkerError>;
+    fn abort_thread_worker(&mut self, name: &str) -> Result<(), ThreadWorkerError>;
+}
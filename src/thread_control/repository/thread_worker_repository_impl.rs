@@ -1,23 +1,54 @@
-use std::collections::HashMap;
-use std::future::Future;
-use std::ops::Deref;
-use std::pin::Pin;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::{Arc, Mutex};
-use async_trait::async_trait;
+use std::thread;
+use std::time::Duration;
 use lazy_static::lazy_static;
-use tokio::runtime::Handle;
-use tokio::task;
-use crate::thread_control::entity::thread_worker::ThreadWorker;
+use tokio::runtime::{Builder, Handle};
+use tokio::sync::{watch, Notify};
+use tokio::task::LocalSet;
+use tokio_util::sync::CancellationToken;
+use crate::thread_control::entity::health_report::HealthReport;
+use crate::thread_control::entity::mailbox_address::MailboxAddress;
+use crate::thread_control::entity::supervision_policy::SupervisionPolicy;
+use crate::thread_control::entity::thread_worker::{BlockingThreadWorkerFunction, LocalThreadWorkerFunction, ThreadWorker, ThreadWorkerContextFunction, ThreadWorkerFunction, ThreadWorkerFuture, ThreadWorkerOutputFunction};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+use crate::thread_control::entity::worker_failure::WorkerFailure;
+use crate::thread_control::entity::worker_group::{WorkerGroup, WorkerGroupJob};
+use crate::thread_control::entity::worker_group_config::WorkerGroupConfig;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::entity::worker_health::WorkerHealth;
+use crate::thread_control::entity::worker_liveness::WorkerLiveness;
+use crate::thread_control::entity::worker_mailbox::WorkerMailbox;
+use crate::thread_control::entity::worker_output::WorkerOutput;
+use crate::thread_control::entity::worker_readiness::WorkerReadiness;
+use crate::thread_control::entity::worker_schedule::WorkerSchedule;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
 use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::scheduler::thread_worker_scheduler::ThreadWorkerScheduler;
 
 pub struct ThreadWorkerRepositoryImpl {
     thread_worker_list: HashMap<String, ThreadWorker>,
+    worker_handle_list: HashMap<String, WorkerHandle>,
+    worker_group_list: HashMap<String, Arc<WorkerGroup>>,
+    shutdown_token: CancellationToken,
+}
+
+// Where start_thread_worker puts a worker
+enum WorkerPlacement {
+    Runtime(Handle),
+    DedicatedThread,
+    ThreadPool(Arc<WorkerGroup>),
 }
 
 impl ThreadWorkerRepositoryImpl {
     pub fn new() -> Self {
         ThreadWorkerRepositoryImpl {
             thread_worker_list: HashMap::new(),
+            worker_handle_list: HashMap::new(),
+            worker_group_list: HashMap::new(),
+            shutdown_token: CancellationToken::new(),
         }
     }
 
@@ -32,66 +63,570 @@ impl ThreadWorkerRepositoryImpl {
     pub fn get_thread_worker_list(&self) -> &HashMap<String, ThreadWorker> {
         &self.thread_worker_list
     }
+
+    pub fn get_worker_handle_list(&self) -> &HashMap<String, WorkerHandle> {
+        &self.worker_handle_list
+    }
+
+    pub fn get_worker_group_list(&self) -> &HashMap<String, Arc<WorkerGroup>> {
+        &self.worker_group_list
+    }
+
+    pub fn find_all_by_state(&self, state: ThreadWorkerState) -> Vec<ThreadWorker> {
+        self.find_all_sorted_by_name(|worker| worker.state() == state)
+    }
+
+    pub fn find_all_alive(&self) -> Vec<ThreadWorker> {
+        self.find_all_sorted_by_name(|worker| worker.state().is_alive())
+    }
+
+    fn find_all_sorted_by_name(&self, predicate: impl Fn(&ThreadWorker) -> bool) -> Vec<ThreadWorker> {
+        let mut thread_worker_list: Vec<ThreadWorker> = self.thread_worker_list
+            .values()
+            .filter(|worker| predicate(worker))
+            .cloned()
+            .collect();
+
+        thread_worker_list.sort_by(|first, second| first.name().cmp(second.name()));
+        thread_worker_list
+    }
+
+    // Dependencies come before their dependents; ties are broken by name so the order is stable
+    pub fn find_start_order(&self) -> Result<Vec<String>, ThreadWorkerError> {
+        let mut pending_dependency_count: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut dependent_list: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for worker in self.thread_worker_list.values() {
+            for dependency_name in worker.get_dependency_list() {
+                if !self.thread_worker_list.contains_key(dependency_name) {
+                    return Err(ThreadWorkerError::MissingDependency {
+                        name: worker.name().to_string(),
+                        dependency: dependency_name.clone(),
+                    });
+                }
+
+                dependent_list.entry(dependency_name.as_str()).or_default().push(worker.name());
+            }
+
+            pending_dependency_count.insert(worker.name(), worker.get_dependency_list().len());
+        }
+
+        let mut startable_list: BTreeSet<&str> = pending_dependency_count
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(name, _)| *name)
+            .collect();
+        let mut start_order = Vec::new();
+
+        while let Some(name) = startable_list.pop_first() {
+            start_order.push(name.to_string());
+
+            for dependent_name in dependent_list.get(name).into_iter().flatten() {
+                if let Some(count) = pending_dependency_count.get_mut(dependent_name) {
+                    *count -= 1;
+                    if *count == 0 {
+                        startable_list.insert(dependent_name);
+                    }
+                }
+            }
+        }
+
+        // Whatever never became startable is on a cycle or waits behind one
+        if start_order.len() < pending_dependency_count.len() {
+            let blocked_list: Vec<&str> = pending_dependency_count
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(name, _)| *name)
+                .collect();
+
+            return Err(ThreadWorkerError::DependencyCycle(blocked_list.join(", ")));
+        }
+
+        Ok(start_order)
+    }
+
+    // Alive workers that take part in a dependency, dependents first.
+    // Everything else is left to begin_shutdown, and so is everything if the graph has a cycle.
+    pub fn find_dependency_shutdown_order(&self) -> Vec<String> {
+        let start_order = self.find_start_order().unwrap_or_default();
+
+        let linked_name_list: BTreeSet<&str> = self.thread_worker_list
+            .values()
+            .filter(|worker| !worker.get_dependency_list().is_empty())
+            .flat_map(|worker| worker.get_dependency_list().iter().map(String::as_str).chain([worker.name()]))
+            .collect();
+
+        start_order
+            .into_iter()
+            .rev()
+            .filter(|name| linked_name_list.contains(name.as_str()))
+            .filter(|name| self.thread_worker_list.get(name).is_some_and(|worker| worker.state().is_alive()))
+            .collect()
+    }
+
+    // Gives the worker a mailbox for messages of type M. Attaching again only takes effect from the next run:
+    // a running worker keeps reading the old mailbox, and addresses taken from it still reach that run
+    pub fn attach_mailbox<M: Send + 'static>(&mut self, name: &str, capacity: usize) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.set_mailbox(WorkerMailbox::new::<M>(capacity));
+        Ok(())
+    }
+
+    // Take the address out of the lock before awaiting send or request on it
+    pub fn find_mailbox_address<M: Send + 'static>(&self, name: &str) -> Result<MailboxAddress<M>, ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.get_mailbox()
+            .ok_or_else(|| ThreadWorkerError::NoMailbox(name.to_string()))?
+            .address(name)
+    }
+
+    pub fn try_send<M: Send + 'static>(&self, name: &str, message: M) -> Result<(), ThreadWorkerError> {
+        self.find_mailbox_address(name)?.try_send(message)
+    }
+
+    pub fn get_shutdown_token(&self) -> CancellationToken {
+        self.shutdown_token.clone()
+    }
+
+    // Signals every worker to wind down and hands back the handles still worth waiting on.
+    // Nothing is aborted here; forcing stragglers is left to the caller once its deadline passes.
+    pub fn begin_shutdown(&mut self) -> Vec<WorkerHandle> {
+        self.shutdown_token.cancel();
+
+        let mut worker_handle_list = Vec::new();
+
+        for worker in self.find_all_alive() {
+            worker.transition_to(ThreadWorkerState::Stopping);
+
+            if let Some(worker_handle) = self.worker_handle_list.get(worker.name()) {
+                worker_handle_list.push(worker_handle.clone());
+            }
+        }
+
+        worker_handle_list
+    }
+}
+
+impl Default for ThreadWorkerRepositoryImpl {
+    fn default() -> Self {
+        ThreadWorkerRepositoryImpl::new()
+    }
 }
 
-#[async_trait]
 impl ThreadWorkerRepositoryTrait for ThreadWorkerRepositoryImpl {
     fn save_thread_worker(
         &mut self,
         name: &str,
-        will_be_execute_function: Option<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send + 'static>>,
+        will_be_execute_function: Option<ThreadWorkerFunction>,
     ) {
         let thread_worker = ThreadWorker::new(name, will_be_execute_function);
         self.thread_worker_list.insert(name.to_string(), thread_worker);
     }
 
+    fn save_thread_worker_with_context(
+        &mut self,
+        name: &str,
+        will_be_execute_context_function: ThreadWorkerContextFunction,
+    ) {
+        let thread_worker = ThreadWorker::new_with_context(name, will_be_execute_context_function);
+        self.thread_worker_list.insert(name.to_string(), thread_worker);
+    }
+
+    fn save_local_thread_worker(
+        &mut self,
+        name: &str,
+        will_be_execute_local_function: LocalThreadWorkerFunction,
+    ) {
+        let thread_worker = ThreadWorker::new_local(name, will_be_execute_local_function);
+        self.thread_worker_list.insert(name.to_string(), thread_worker);
+    }
+
+    fn save_blocking_thread_worker(
+        &mut self,
+        name: &str,
+        will_be_execute_blocking_function: BlockingThreadWorkerFunction,
+    ) {
+        let will_be_execute_blocking_function = Arc::new(will_be_execute_blocking_function);
+
+        // The worker future only waits for the blocking thread, so the runtime it sits on stays free
+        let blocking_function = move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let will_be_execute_blocking_function = Arc::clone(&will_be_execute_blocking_function);
+            Box::pin(async move {
+                let join_result = tokio::task::spawn_blocking(move || will_be_execute_blocking_function(context)).await;
+
+                // Re-raised so the worker reports Panicked like any other worker would
+                if let Err(error) = join_result {
+                    if error.is_panic() {
+                        std::panic::resume_unwind(error.into_panic());
+                    }
+                }
+            })
+        };
+
+        let thread_worker = ThreadWorker::new_with_context(name, Box::new(blocking_function)).with_blocking();
+        self.thread_worker_list.insert(name.to_string(), thread_worker);
+    }
+
+    fn save_scheduled_thread_worker(
+        &mut self,
+        name: &str,
+        schedule: WorkerSchedule,
+        will_be_execute_context_function: ThreadWorkerContextFunction,
+    ) {
+        let scheduled_function = ThreadWorkerScheduler::schedule(schedule.clone(), will_be_execute_context_function);
+        let thread_worker = ThreadWorker::new_with_context(name, scheduled_function).with_schedule(schedule);
+        self.thread_worker_list.insert(name.to_string(), thread_worker);
+    }
+
+    // Registers a worker whose future yields a value; the latest value is kept per worker until taken.
+    // Fallible workers simply use a Result as their output type.
+    fn save_thread_worker_with_output<T: Send + 'static>(
+        &mut self,
+        name: &str,
+        will_be_execute_function: ThreadWorkerOutputFunction<T>,
+    ) {
+        let output = WorkerOutput::for_type::<T>();
+        let worker_output = output.clone();
+
+        let output_function = move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let future = will_be_execute_function(context);
+            let worker_output = worker_output.clone();
+            Box::pin(async move {
+                worker_output.store(future.await);
+            })
+        };
+
+        let thread_worker = ThreadWorker::new_with_context(name, Box::new(output_function)).with_output(output);
+        self.thread_worker_list.insert(name.to_string(), thread_worker);
+    }
+
+    fn take_output<T: 'static>(&self, name: &str) -> Result<T, ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.get_output().take(name)
+    }
+
+    fn set_supervision_policy(&mut self, name: &str, supervision_policy: SupervisionPolicy) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.set_supervision_policy(supervision_policy);
+        Ok(())
+    }
+
+    // Replacing a group leaves workers already running on the old one where they are;
+    // each of them holds on to the old group until it exits
+    fn save_worker_group(&mut self, worker_group_config: WorkerGroupConfig) -> Result<(), ThreadWorkerError> {
+        let worker_group = WorkerGroup::build(worker_group_config)?;
+        self.worker_group_list.insert(worker_group.name().to_string(), Arc::new(worker_group));
+        Ok(())
+    }
+
+    fn assign_worker_group(&mut self, name: &str, worker_group_name: &str) -> Result<(), ThreadWorkerError> {
+        let worker_group = self.worker_group_list
+            .get(worker_group_name)
+            .ok_or_else(|| ThreadWorkerError::WorkerGroupNotFound(worker_group_name.to_string()))?;
+
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        if worker.is_local() && !worker_group.supports_local_workers() {
+            return Err(ThreadWorkerError::InvalidWorkerGroup(format!("local worker {} cannot run on Tokio runtime group {}", name, worker_group_name)));
+        }
+
+        worker.set_worker_group(worker_group_name);
+        Ok(())
+    }
+
+    // The dependency does not have to be registered yet; it is looked up when the start order is built
+    fn add_dependency(&mut self, name: &str, dependency_name: &str) -> Result<(), ThreadWorkerError> {
+        if name == dependency_name {
+            return Err(ThreadWorkerError::DependencyCycle(name.to_string()));
+        }
+
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.add_dependency(dependency_name);
+        Ok(())
+    }
+
+    fn require_readiness_signal(&mut self, name: &str) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.set_signals_readiness(true);
+        Ok(())
+    }
+
+    fn set_liveness_timeout(&mut self, name: &str, liveness_timeout: Duration) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get_mut(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        worker.set_liveness_timeout(liveness_timeout);
+        Ok(())
+    }
+
     fn find_by_name(&self, name: &str) -> Option<ThreadWorker> {
         self.thread_worker_list.get(name).cloned()
     }
 
-    async fn start_thread_worker(&self, name: &str) {
+    fn find_worker_handle(&self, name: &str) -> Option<WorkerHandle> {
+        self.worker_handle_list.get(name).cloned()
+    }
+
+    // One entry per registered worker, sorted by name, for readiness and liveness probes to poll
+    fn get_health_report(&self) -> HealthReport {
+        let mut worker_list: Vec<&ThreadWorker> = self.thread_worker_list.values().collect();
+        worker_list.sort_by(|first, second| first.name().cmp(second.name()));
+
+        let mut health_report = HealthReport::new();
+
+        for worker in worker_list {
+            let mut worker_health = WorkerHealth::new(worker.name(), worker.state())
+                .with_liveness_timeout(worker.get_liveness_timeout());
+
+            if let Some(worker_handle) = self.worker_handle_list.get(worker.name()) {
+                worker_health = worker_health
+                    .with_ready(worker_handle.is_ready())
+                    .with_since_last_heartbeat(worker_handle.since_last_heartbeat());
+            }
+
+            health_report.record(worker_health);
+        }
+
+        health_report
+    }
+
+    fn start_thread_worker(&mut self, name: &str) -> Result<WorkerHandle, ThreadWorkerError> {
         let thread_worker_list = self.get_thread_worker_list();
 
-        if let Some(worker) = thread_worker_list.get(name) {
-            if let Some(function_arc_ref) = worker.get_will_be_execute_function_ref() {
-                // function_arc_ref의 타입: &Arc<Mutex<Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>>
-                let guard = function_arc_ref.lock().await;
-                let box_function = &guard;
-
-                let fucking_gpt_test = &*guard;
-                let shit_gpt_test= &**fucking_gpt_test;
-
-                // &dyn Fn()
-                // let closure: &dyn Fn() = &*guard;
-
-                // custom_function: fn() -> Pin<Box<dyn Future<Output=()>>>
-                //                          Pin<Box<dyn Future<Output=()>>>
-                // let future: &dyn Fn() = shit_gpt_test();
-                // future;
-
-                let future = shit_gpt_test();
-                // let runtime = tokio::runtime::Runtime::new().unwrap();
-                // runtime.block_on(future);
-                task::block_in_place(move || {
-                    Handle::current().block_on(async move {
-                        future.await
+        let worker = thread_worker_list
+            .get(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        if !worker.has_function() {
+            return Err(ThreadWorkerError::NoFunction(name.to_string()));
+        }
+
+        let placement = match worker.get_worker_group() {
+            Some(worker_group_name) => {
+                let worker_group = self.worker_group_list
+                    .get(worker_group_name)
+                    .ok_or_else(|| ThreadWorkerError::WorkerGroupNotFound(worker_group_name.to_string()))?;
+
+                match worker_group.get_runtime_handle() {
+                    Some(_) if worker.is_local() => {
+                        return Err(ThreadWorkerError::InvalidWorkerGroup(format!("local worker {} cannot run on Tokio runtime group {}", name, worker_group_name)));
+                    }
+                    Some(runtime_handle) => WorkerPlacement::Runtime(runtime_handle),
+                    None => WorkerPlacement::ThreadPool(Arc::clone(worker_group)),
+                }
+            }
+            None if worker.is_local() => WorkerPlacement::DedicatedThread,
+            // Send workers outside a group are spawned onto the caller's runtime, so there has to be one
+            None => WorkerPlacement::Runtime(Handle::try_current().map_err(|_| ThreadWorkerError::RuntimeUnavailable(name.to_string()))?),
+        };
+
+        if !worker.transition_to(ThreadWorkerState::Running) {
+            return Err(ThreadWorkerError::AlreadyRunning(name.to_string()));
+        }
+
+        // A value left over from an earlier run must not be mistaken for this run's result
+        worker.get_output().clear();
+
+        let (exit_sender, exit_receiver) = watch::channel(None);
+        let abort_notify = Arc::new(Notify::new());
+        let worker_abort_notify = Arc::clone(&abort_notify);
+        let running_worker = worker.clone();
+        let exiting_worker = worker.clone();
+
+        // Every run gets a fresh token so a restarted worker is not born cancelled,
+        // while still hanging off the shutdown token so shutdown reaches it too
+        let cancellation_token = self.shutdown_token.child_token();
+        let readiness = WorkerReadiness::new();
+        let liveness = WorkerLiveness::new();
+        let failure = WorkerFailure::new();
+        let context = ThreadWorkerContext::new(name, cancellation_token.clone())
+            .with_readiness(readiness.clone())
+            .with_liveness(liveness.clone())
+            .with_failure(failure.clone())
+            .with_mailbox(worker.get_mailbox().cloned());
+
+        // Workers that never signal count as ready as soon as they are started
+        if !worker.signals_readiness() {
+            readiness.mark_ready();
+        }
+
+        let worker_group = worker.get_worker_group()
+            .and_then(|worker_group_name| self.worker_group_list.get(worker_group_name))
+            .cloned();
+
+        let report_exit = move |exit_status: WorkerExitStatus| {
+            // A worker that recorded a failure and then returned did not complete its work
+            let exit_status = match (exit_status, failure.take()) {
+                (WorkerExitStatus::Completed, Some(message)) => WorkerExitStatus::Failed(message),
+                (exit_status, _) => exit_status,
+            };
+
+            exiting_worker.transition_to(ThreadWorkerState::from_exit_status(&exit_status));
+            let _ = exit_sender.send(Some(exit_status));
+
+            // Released only after reporting, so a replaced group keeps its threads until its last worker is done
+            drop(worker_group);
+        };
+
+        match placement {
+            WorkerPlacement::Runtime(runtime_handle) => {
+                let worker_task = runtime_handle.spawn(async move {
+                    let future = match running_worker.create_future(context).await {
+                        Some(future) => future,
+                        None => return WorkerExitStatus::Completed,
+                    };
+
+                    tokio::select! {
+                        _ = future => WorkerExitStatus::Completed,
+                        _ = worker_abort_notify.notified() => WorkerExitStatus::Cancelled,
+                    }
+                });
+
+                // Awaiting the task from a second one is what turns a panic into a reported exit status
+                runtime_handle.spawn(async move {
+                    report_exit(WorkerExitStatus::from_join_result(worker_task.await));
+                });
+            }
+            WorkerPlacement::DedicatedThread => {
+                // A !Send future can only live on the thread that created it, so local workers
+                // get their own OS thread driving a current-thread runtime and a LocalSet
+                let spawn_result = thread::Builder::new()
+                    .name(worker.name().to_string())
+                    .spawn(move || {
+                        let runtime = match Builder::new_current_thread().enable_all().build() {
+                            Ok(runtime) => runtime,
+                            Err(error) => {
+                                report_exit(WorkerExitStatus::Panicked(error.to_string()));
+                                return;
+                            }
+                        };
+
+                        let local_set = LocalSet::new();
+                        let join_result = local_set.block_on(&runtime, async move {
+                            tokio::task::spawn_local(run_local_worker(running_worker, context, worker_abort_notify)).await
+                        });
+
+                        report_exit(WorkerExitStatus::from_join_result(join_result));
                     });
+
+                if let Err(error) = spawn_result {
+                    worker.transition_to(ThreadWorkerState::Failed);
+                    return Err(ThreadWorkerError::SpawnFailed(error.to_string()));
+                }
+            }
+            WorkerPlacement::ThreadPool(worker_group) => {
+                let job: WorkerGroupJob = Box::new(move || {
+                    Box::pin(async move {
+                        let join_result = tokio::task::spawn_local(run_local_worker(running_worker, context, worker_abort_notify)).await;
+                        report_exit(WorkerExitStatus::from_join_result(join_result));
+                    })
                 });
 
-                // Add an assertion to check if the worker name matches
-                assert_eq!(worker.name(), name);
+                if let Err(error) = worker_group.dispatch_job(job) {
+                    worker.transition_to(ThreadWorkerState::Failed);
+                    return Err(error);
+                }
+            }
+        }
+
+        let worker_handle = WorkerHandle::new(
+            name,
+            exit_receiver,
+            abort_notify,
+            cancellation_token,
+            worker.get_output().clone(),
+        )
+        .with_readiness(readiness)
+        .with_liveness(liveness);
+        self.worker_handle_list.insert(name.to_string(), worker_handle.clone());
+
+        Ok(worker_handle)
+    }
+
+    fn stop_thread_worker(&mut self, name: &str) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        // Only a running worker has anything to stop. Between runs the last run's token is still cancelled,
+        // since that is what a supervisor waiting out its restart backoff watches
+        if !worker.transition_to(ThreadWorkerState::Stopping) {
+            if let Some(worker_handle) = self.worker_handle_list.get(name) {
+                worker_handle.cancel();
+            }
+            return Ok(());
+        }
+
+        if let Some(worker_handle) = self.worker_handle_list.get(name) {
+            if worker.watches_cancellation() {
+                worker_handle.cancel();
             } else {
-                panic!("Thread worker function not found: {}", name);
+                worker_handle.abort();
             }
-        } else {
-            panic!("Thread worker not found: {}", name);
         }
+
+        Ok(())
+    }
+
+    fn abort_thread_worker(&mut self, name: &str) -> Result<(), ThreadWorkerError> {
+        let worker = self.thread_worker_list
+            .get(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        if !worker.state().is_alive() {
+            return Ok(());
+        }
+
+        worker.transition_to(ThreadWorkerState::Stopping);
+
+        if let Some(worker_handle) = self.worker_handle_list.get(name) {
+            worker_handle.abort();
+        }
+
+        Ok(())
+    }
+}
+
+// Runs on a thread with a LocalSet; plain Send workers take this path too when placed on such a thread
+async fn run_local_worker(running_worker: ThreadWorker, context: ThreadWorkerContext, abort_notify: Arc<Notify>) -> WorkerExitStatus {
+    let future = match running_worker.create_local_future(context).await {
+        Some(future) => future,
+        None => return WorkerExitStatus::Completed,
+    };
+
+    tokio::select! {
+        _ = future => WorkerExitStatus::Completed,
+        _ = abort_notify.notified() => WorkerExitStatus::Cancelled,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use tokio::sync::Barrier;
     use tokio::test;
 
     fn my_sync_function() {
@@ -108,27 +643,30 @@ mod tests {
         let instance2 = ThreadWorkerRepositoryImpl::get_instance();
 
         // Ensure that both instances are the same
-        assert_eq!(Arc::ptr_eq(&instance1, &instance2), true);
+        assert!(Arc::ptr_eq(&instance1, &instance2));
     }
 
     #[tokio::test]
     async fn test_save_thread_worker() {
         let repository = ThreadWorkerRepositoryImpl::get_instance();
 
-        // Lock the mutex to access the repository
-        let mut repository = repository.lock().unwrap();
-
-        let custom_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 println!("Custom function executed!");
             })
         };
 
         // Save a thread worker
-        repository.save_thread_worker("TestWorker", Some(Box::new(custom_function)));
+        let saved_worker = {
+            // Lock the mutex to access the repository
+            let mut repository = repository.lock().unwrap();
+
+            repository.save_thread_worker("TestWorker", Some(Box::new(custom_function)));
+            repository.thread_worker_list.get("TestWorker").cloned()
+        };
 
         // Retrieve the saved worker and execute its function
-        if let Some(worker) = repository.thread_worker_list.get("TestWorker") {
+        if let Some(worker) = saved_worker {
             let function_arc = Arc::clone(&worker.get_will_be_execute_function().unwrap());
 
             // Lock the Mutex to get the guard
@@ -152,21 +690,24 @@ mod tests {
     async fn test_save_sync_thread_worker() {
         let repository = ThreadWorkerRepositoryImpl::get_instance();
 
-        // Lock the mutex to access the repository
-        let mut repository = repository.lock().unwrap();
-
         // Synchronous function
         let sync_custom_function = || {
             Box::pin(async {
                 my_sync_function();
-            }) as Pin<Box<dyn Future<Output = ()>>>
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
         };
 
         // Save a thread worker with a synchronous function
-        repository.save_thread_worker("SyncTestWorker", Some(Box::new(sync_custom_function)));
+        let saved_worker = {
+            // Lock the mutex to access the repository
+            let mut repository = repository.lock().unwrap();
+
+            repository.save_thread_worker("SyncTestWorker", Some(Box::new(sync_custom_function)));
+            repository.thread_worker_list.get("SyncTestWorker").cloned()
+        };
 
         // Retrieve and execute the saved worker's function
-        if let Some(worker) = repository.thread_worker_list.get("SyncTestWorker") {
+        if let Some(worker) = saved_worker {
             let function_arc = Arc::clone(&worker.get_will_be_execute_function().unwrap());
 
             // Lock the Mutex to get the guard
@@ -190,21 +731,24 @@ mod tests {
     async fn test_save_async_thread_worker() {
         let repository = ThreadWorkerRepositoryImpl::get_instance();
 
-        // Lock the mutex to access the repository
-        let mut repository = repository.lock().unwrap();
-
         // Asynchronous function
         let async_custom_function = || {
             Box::pin(async {
                 my_async_function().await;
-            }) as Pin<Box<dyn Future<Output = ()>>>
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
         };
 
         // Save a thread worker with an asynchronous function
-        repository.save_thread_worker("AsyncTestWorker", Some(Box::new(async_custom_function)));
+        let saved_worker = {
+            // Lock the mutex to access the repository
+            let mut repository = repository.lock().unwrap();
+
+            repository.save_thread_worker("AsyncTestWorker", Some(Box::new(async_custom_function)));
+            repository.thread_worker_list.get("AsyncTestWorker").cloned()
+        };
 
         // Retrieve and execute the saved worker's function
-        if let Some(worker) = repository.thread_worker_list.get("AsyncTestWorker") {
+        if let Some(worker) = saved_worker {
             let function_arc = Arc::clone(&worker.get_will_be_execute_function().unwrap());
 
             // Lock the Mutex to get the guard
@@ -250,46 +794,718 @@ mod tests {
         // Execute the async code within the tokio runtime
         let repository = ThreadWorkerRepositoryImpl::get_instance();
 
-        // Lock the mutex to access the repository
-        let mut repository = repository.lock().unwrap();
-
-        let custom_function = || -> Pin<Box<dyn Future<Output=()>>>   {
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>>   {
             Box::pin(async {
                 println!("Custom function executed!");
             })
         };
 
-        // tokio::runtime::Builder::new_multi_thread()
-        //     .worker_threads(1)
-        //     .enable_all()
-        //     .build()
-        //     .unwrap()
-        //     .block_on(async {
-        //         custom_function
-        //     });
+        let worker_handle = {
+            // Lock the mutex to access the repository
+            let mut repository = repository.lock().unwrap();
 
-        // Save a thread worker
-        repository.save_thread_worker("TestWorker", Some(Box::new(custom_function)));
+            // Save a thread worker
+            repository.save_thread_worker("TestWorker", Some(Box::new(custom_function)));
+            repository.start_thread_worker("TestWorker").unwrap()
+        };
 
-        repository.start_thread_worker("TestWorker").await;
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_new_save_sync_thread_worker() {
         let repository = ThreadWorkerRepositoryImpl::get_instance();
 
-        // Lock the mutex to access the repository
-        let mut repository = repository.lock().unwrap();
-
         // Synchronous function
         let sync_custom_function = || {
             Box::pin(async {
                 my_sync_function();
-            }) as Pin<Box<dyn Future<Output = ()>>>
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
         };
 
-        // Save a thread worker with a synchronous function
-        repository.save_thread_worker("SyncTestWorker", Some(Box::new(sync_custom_function)));
-        repository.start_thread_worker("SyncTestWorker").await;
+        let worker_handle = {
+            // Lock the mutex to access the repository
+            let mut repository = repository.lock().unwrap();
+
+            // Save a thread worker with a synchronous function
+            repository.save_thread_worker("SyncTestWorker", Some(Box::new(sync_custom_function)));
+            repository.start_thread_worker("SyncTestWorker").unwrap()
+        };
+
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_start_thread_worker_on_current_thread_runtime() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                println!("Started from a current-thread runtime!");
+            })
+        };
+
+        repository.save_thread_worker("CurrentThreadWorker", Some(Box::new(custom_function)));
+
+        let worker_handle = repository.start_thread_worker("CurrentThreadWorker").unwrap();
+        assert_eq!(worker_handle.name(), "CurrentThreadWorker");
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_start_thread_workers_concurrently() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        // Both workers block on the barrier, so this only completes if they run at the same time
+        let barrier = Arc::new(Barrier::new(2));
+
+        for name in ["ConcurrentWorker1", "ConcurrentWorker2"] {
+            let barrier = Arc::clone(&barrier);
+            let custom_function = move || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+                let barrier = Arc::clone(&barrier);
+                Box::pin(async move {
+                    barrier.wait().await;
+                })
+            };
+
+            repository.save_thread_worker(name, Some(Box::new(custom_function)));
+        }
+
+        let first = repository.start_thread_worker("ConcurrentWorker1").unwrap();
+        let second = repository.start_thread_worker("ConcurrentWorker2").unwrap();
+
+        assert_eq!(first.join().await, WorkerExitStatus::Completed);
+        assert_eq!(second.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_abort_thread_worker() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let endless_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            })
+        };
+
+        repository.save_thread_worker("EndlessWorker", Some(Box::new(endless_function)));
+
+        let worker_handle = repository.start_thread_worker("EndlessWorker").unwrap();
+        assert!(!worker_handle.is_finished());
+
+        worker_handle.abort();
+
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Cancelled);
+        assert!(worker_handle.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_panicked_thread_worker() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let panic_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                panic!("Worker failed on purpose");
+            })
+        };
+
+        repository.save_thread_worker("PanicWorker", Some(Box::new(panic_function)));
+
+        let worker_handle = repository.start_thread_worker("PanicWorker").unwrap();
+
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Panicked("Worker failed on purpose".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_thread_worker() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let fail_function = |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            Box::pin(async move {
+                context.fail("Worker gave up on purpose");
+            })
+        };
+
+        repository.save_thread_worker_with_context("FailWorker", Box::new(fail_function));
+
+        let worker_handle = repository.start_thread_worker("FailWorker").unwrap();
+
+        assert_eq!(
+            worker_handle.join().await,
+            WorkerExitStatus::Failed("Worker gave up on purpose".to_string())
+        );
+        assert_eq!(repository.find_by_name("FailWorker").unwrap().state(), ThreadWorkerState::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_find_worker_handle() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {})
+        };
+
+        repository.save_thread_worker("HandleLookupWorker", Some(Box::new(custom_function)));
+        assert!(repository.find_worker_handle("HandleLookupWorker").is_none());
+
+        repository.start_thread_worker("HandleLookupWorker").unwrap();
+
+        let worker_handle = repository.find_worker_handle("HandleLookupWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        assert_eq!(
+            repository.find_worker_handle("HandleLookupWorker").unwrap().exit_status(),
+            Some(WorkerExitStatus::Completed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_thread_worker_lifecycle_state() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let endless_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            })
+        };
+
+        let panic_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                panic!("Worker failed on purpose");
+            })
+        };
+
+        repository.save_thread_worker("LifecycleEndlessWorker", Some(Box::new(endless_function)));
+        repository.save_thread_worker("LifecyclePanicWorker", Some(Box::new(panic_function)));
+        repository.save_thread_worker("LifecycleIdleWorker", None);
+
+        let registered: Vec<String> = repository.find_all_by_state(ThreadWorkerState::Registered)
+            .iter()
+            .map(|worker| worker.name().to_string())
+            .collect();
+        assert_eq!(registered, vec!["LifecycleEndlessWorker", "LifecycleIdleWorker", "LifecyclePanicWorker"]);
+
+        let endless_handle = repository.start_thread_worker("LifecycleEndlessWorker").unwrap();
+        let panic_handle = repository.start_thread_worker("LifecyclePanicWorker").unwrap();
+        panic_handle.join().await;
+
+        assert_eq!(repository.find_by_name("LifecycleEndlessWorker").unwrap().state(), ThreadWorkerState::Running);
+        assert_eq!(repository.find_by_name("LifecyclePanicWorker").unwrap().state(), ThreadWorkerState::Failed);
+        assert_eq!(repository.find_all_alive().len(), 1);
+
+        repository.stop_thread_worker("LifecycleEndlessWorker").unwrap();
+        endless_handle.join().await;
+
+        assert_eq!(repository.find_by_name("LifecycleEndlessWorker").unwrap().state(), ThreadWorkerState::Finished);
+        assert!(repository.find_all_alive().is_empty());
+        assert_eq!(repository.find_all_by_state(ThreadWorkerState::Finished).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_start_thread_worker_errors() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        assert_eq!(
+            repository.start_thread_worker("MissingWorker").unwrap_err(),
+            ThreadWorkerError::NotFound("MissingWorker".to_string())
+        );
+        assert_eq!(
+            repository.stop_thread_worker("MissingWorker").unwrap_err(),
+            ThreadWorkerError::NotFound("MissingWorker".to_string())
+        );
+
+        repository.save_thread_worker("FunctionlessWorker", None);
+        assert_eq!(
+            repository.start_thread_worker("FunctionlessWorker").unwrap_err(),
+            ThreadWorkerError::NoFunction("FunctionlessWorker".to_string())
+        );
+
+        let endless_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            })
+        };
+
+        repository.save_thread_worker("DuplicateWorker", Some(Box::new(endless_function)));
+
+        let worker_handle = repository.start_thread_worker("DuplicateWorker").unwrap();
+        assert_eq!(
+            repository.start_thread_worker("DuplicateWorker").unwrap_err(),
+            ThreadWorkerError::AlreadyRunning("DuplicateWorker".to_string())
+        );
+
+        repository.stop_thread_worker("DuplicateWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_stop_context_thread_worker_cooperatively() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let context_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async move {
+                assert_eq!(context.name(), "CooperativeWorker");
+                context.cancelled().await;
+            })
+        };
+
+        repository.save_thread_worker_with_context("CooperativeWorker", Box::new(context_function));
+
+        let first_run = repository.start_thread_worker("CooperativeWorker").unwrap();
+        repository.stop_thread_worker("CooperativeWorker").unwrap();
+
+        // Returning from the function is a clean exit, unlike a forced abort
+        assert_eq!(first_run.join().await, WorkerExitStatus::Completed);
+
+        let second_run = repository.start_thread_worker("CooperativeWorker").unwrap();
+        assert!(!second_run.cancellation_token().is_cancelled());
+
+        repository.get_shutdown_token().cancel();
+        assert_eq!(second_run.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_start_local_thread_worker() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let local_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()>>> {
+            // Rc is !Send, so this worker can only run through the LocalSet path
+            let shared_name = std::rc::Rc::new(context.name().to_string());
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                assert_eq!(std::thread::current().name(), Some(shared_name.as_str()));
+            })
+        };
+
+        repository.save_local_thread_worker("LocalWorker", Box::new(local_function));
+
+        let worker_handle = repository.start_thread_worker("LocalWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_start_thread_worker_without_runtime() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let custom_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {})
+        };
+
+        repository.save_thread_worker("RuntimelessWorker", Some(Box::new(custom_function)));
+
+        // A plain OS thread has no runtime context to spawn onto
+        let (repository, start_result) = thread::spawn(move || {
+            let start_result = repository.start_thread_worker("RuntimelessWorker");
+            (repository, start_result)
+        }).join().unwrap();
+
+        assert_eq!(
+            start_result.unwrap_err(),
+            ThreadWorkerError::RuntimeUnavailable("RuntimelessWorker".to_string())
+        );
+        assert_eq!(
+            repository.find_by_name("RuntimelessWorker").unwrap().state(),
+            ThreadWorkerState::Registered
+        );
+    }
+
+    #[tokio::test]
+    async fn test_thread_worker_with_output() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        let fetch_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = Result<usize, String>> + Send>> {
+            Box::pin(async move {
+                match context.name() {
+                    "FetchWorker" => Ok(3),
+                    other => Err(format!("Unexpected worker {}", other)),
+                }
+            })
+        };
+
+        repository.save_thread_worker_with_output("FetchWorker", Box::new(fetch_function));
+        assert_eq!(
+            repository.find_by_name("FetchWorker").unwrap().get_output_type_name(),
+            Some(std::any::type_name::<Result<usize, String>>())
+        );
+
+        let worker_handle = repository.start_thread_worker("FetchWorker").unwrap();
+        assert_eq!(worker_handle.join_output::<Result<usize, String>>().await, Ok(Ok(3)));
+
+        // The repository keeps the latest value until someone takes it
+        let worker_handle = repository.start_thread_worker("FetchWorker").unwrap();
+        worker_handle.join().await;
+
+        assert_eq!(
+            repository.take_output::<usize>("FetchWorker"),
+            Err(ThreadWorkerError::OutputTypeMismatch("FetchWorker".to_string()))
+        );
+        assert_eq!(repository.take_output::<Result<usize, String>>("FetchWorker"), Ok(Ok(3)));
+        assert_eq!(
+            repository.take_output::<Result<usize, String>>("FetchWorker"),
+            Err(ThreadWorkerError::NoOutput("FetchWorker".to_string()))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduled_thread_worker() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+        let tick_count = Arc::new(AtomicUsize::new(0));
+        let worker_tick_count = Arc::clone(&tick_count);
+
+        let heartbeat_function = move |_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let tick_count = Arc::clone(&worker_tick_count);
+            Box::pin(async move {
+                tick_count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        let schedule = WorkerSchedule::fixed_interval(Duration::from_millis(100)).unwrap();
+        repository.save_scheduled_thread_worker("HeartbeatWorker", schedule.clone(), Box::new(heartbeat_function));
+        assert_eq!(repository.find_by_name("HeartbeatWorker").unwrap().get_schedule(), Some(&schedule));
+
+        let worker_handle = repository.start_thread_worker("HeartbeatWorker").unwrap();
+        tokio::time::sleep(Duration::from_millis(250)).await;
+
+        repository.stop_thread_worker("HeartbeatWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        assert_eq!(tick_count.load(Ordering::SeqCst), 3);
+    }
+
+    fn current_thread_name_function() -> ThreadWorkerOutputFunction<Option<String>> {
+        Box::new(|_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = Option<String>> + Send>> {
+            Box::pin(async { thread::current().name().map(str::to_string) })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_worker_group_keeps_blocking_worker_off_caller_runtime() {
+        use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+        use std::time::Duration;
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+        repository.save_worker_group(WorkerGroupConfig::new("Compute", WorkerGroupKind::ThreadPool)).unwrap();
+
+        // Blocks its thread until released; on this single-threaded test runtime that would hang the test
+        let (release_sender, release_receiver) = std::sync::mpsc::channel::<()>();
+        let release_receiver = Arc::new(Mutex::new(release_receiver));
+        let blocking_function = move |_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = Option<String>> + Send>> {
+            let release_receiver = Arc::clone(&release_receiver);
+            Box::pin(async move {
+                release_receiver.lock().unwrap().recv().unwrap();
+                thread::current().name().map(str::to_string)
+            })
+        };
+        repository.save_thread_worker_with_output("CrunchWorker", Box::new(blocking_function));
+        repository.assign_worker_group("CrunchWorker", "Compute").unwrap();
+
+        let worker_handle = repository.start_thread_worker("CrunchWorker").unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        release_sender.send(()).unwrap();
+
+        assert_eq!(worker_handle.join_output::<Option<String>>().await, Ok(Some("Compute-0".to_string())));
+    }
+
+    #[test]
+    async fn test_tokio_runtime_worker_group() {
+        use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+        let worker_group_config = WorkerGroupConfig::new("Network", WorkerGroupKind::TokioRuntime)
+            .with_thread_count(2)
+            .with_thread_name_prefix("net");
+        repository.save_worker_group(worker_group_config).unwrap();
+
+        repository.save_thread_worker_with_output("NetworkWorker", current_thread_name_function());
+        assert_eq!(
+            repository.assign_worker_group("NetworkWorker", "Missing"),
+            Err(ThreadWorkerError::WorkerGroupNotFound("Missing".to_string()))
+        );
+        repository.assign_worker_group("NetworkWorker", "Network").unwrap();
+
+        let worker_handle = repository.start_thread_worker("NetworkWorker").unwrap();
+        let thread_name = worker_handle.join_output::<Option<String>>().await.unwrap().unwrap();
+        assert!(thread_name.starts_with("net-"));
+
+        // A !Send future cannot move between the runtime's threads
+        let local_function = |_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()>>> { Box::pin(async {}) };
+        repository.save_local_thread_worker("LocalWorker", Box::new(local_function));
+        assert!(matches!(repository.assign_worker_group("LocalWorker", "Network"), Err(ThreadWorkerError::InvalidWorkerGroup(_))));
+    }
+
+    #[test]
+    async fn test_local_worker_on_thread_pool_group() {
+        use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+        repository.save_worker_group(WorkerGroupConfig::new("Ui", WorkerGroupKind::ThreadPool).with_thread_count(2)).unwrap();
+
+        let local_function = |_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()>>> {
+            let local_counter = std::rc::Rc::new(std::cell::Cell::new(0));
+            Box::pin(async move {
+                local_counter.set(local_counter.get() + 1);
+                tokio::task::yield_now().await;
+                assert!(thread::current().name().unwrap().starts_with("Ui-"));
+            })
+        };
+        repository.save_local_thread_worker("LocalWorker", Box::new(local_function));
+        repository.assign_worker_group("LocalWorker", "Ui").unwrap();
+
+        let worker_handle = repository.start_thread_worker("LocalWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[test]
+    async fn test_blocking_thread_worker_leaves_runtime_free() {
+        use std::time::Duration;
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        // Blocks until released; run inline on this single-threaded test runtime it would hang the test
+        let (release_sender, release_receiver) = std::sync::mpsc::channel::<()>();
+        let release_receiver = Mutex::new(release_receiver);
+        let blocking_function = move |context: ThreadWorkerContext| {
+            release_receiver.lock().unwrap().recv().unwrap();
+            assert_eq!(context.name(), "FileWorker");
+        };
+        repository.save_blocking_thread_worker("FileWorker", Box::new(blocking_function));
+        assert!(repository.find_by_name("FileWorker").unwrap().is_blocking());
+
+        let worker_handle = repository.start_thread_worker("FileWorker").unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        release_sender.send(()).unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+
+        repository.save_blocking_thread_worker("BrokenFileWorker", Box::new(|_context: ThreadWorkerContext| panic!("disk on fire")));
+        let worker_handle = repository.start_thread_worker("BrokenFileWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Panicked("disk on fire".to_string()));
+    }
+
+    #[test]
+    async fn test_find_start_order() {
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+
+        for name in ["Dispatcher", "Receiver", "Transmitter", "Connection", "Logger"] {
+            repository.save_thread_worker(name, Some(Box::new(|| -> Pin<Box<dyn Future<Output = ()> + Send>> { Box::pin(async {}) })));
+        }
+
+        repository.add_dependency("Transmitter", "Connection").unwrap();
+        repository.add_dependency("Receiver", "Connection").unwrap();
+        repository.add_dependency("Dispatcher", "Receiver").unwrap();
+        repository.add_dependency("Dispatcher", "Receiver").unwrap();
+        assert_eq!(
+            repository.add_dependency("Missing", "Connection"),
+            Err(ThreadWorkerError::NotFound("Missing".to_string()))
+        );
+
+        assert_eq!(
+            repository.find_start_order().unwrap(),
+            vec!["Connection", "Logger", "Receiver", "Dispatcher", "Transmitter"]
+        );
+        assert_eq!(repository.find_by_name("Dispatcher").unwrap().get_dependency_list(), &vec!["Receiver".to_string()]);
+
+        // Only running workers that take part in a dependency are stopped in order
+        assert!(repository.find_dependency_shutdown_order().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_report() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let mut repository = ThreadWorkerRepositoryImpl::new();
+        let wedged = Arc::new(AtomicBool::new(false));
+        let worker_wedged = Arc::clone(&wedged);
+
+        // Reports ready after its first round and beats every second until it gets wedged
+        let probe_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let wedged = Arc::clone(&worker_wedged);
+            Box::pin(async move {
+                loop {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    context.mark_ready();
+
+                    if !wedged.load(Ordering::SeqCst) {
+                        context.heartbeat();
+                    }
+                }
+            })
+        };
+        let panic_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {
+                panic!("Worker failed on purpose");
+            })
+        };
+
+        repository.save_thread_worker_with_context("ProbeWorker", Box::new(probe_function));
+        repository.require_readiness_signal("ProbeWorker").unwrap();
+        repository.set_liveness_timeout("ProbeWorker", Duration::from_secs(2)).unwrap();
+        repository.save_thread_worker("BrokenWorker", Some(Box::new(panic_function)));
+        repository.save_thread_worker("IdleWorker", None);
+
+        repository.start_thread_worker("ProbeWorker").unwrap();
+        let health_report = repository.get_health_report();
+        assert_eq!(health_report.not_ready_worker_names(), vec!["ProbeWorker"]);
+        assert!(health_report.is_live());
+
+        repository.start_thread_worker("BrokenWorker").unwrap().join().await;
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        let health_report = repository.get_health_report();
+        assert_eq!(health_report.get_worker_health_list().len(), 3);
+        assert_eq!(health_report.not_ready_worker_names(), vec!["BrokenWorker"]);
+        assert_eq!(health_report.not_live_worker_names(), vec!["BrokenWorker"]);
+        assert_eq!(
+            health_report.find_by_name("ProbeWorker").unwrap().get_since_last_heartbeat(),
+            Some(Duration::from_millis(500))
+        );
+
+        wedged.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(3)).await;
+
+        let health_report = repository.get_health_report();
+        assert!(health_report.find_by_name("ProbeWorker").unwrap().is_ready());
+        assert_eq!(health_report.not_live_worker_names(), vec!["BrokenWorker", "ProbeWorker"]);
+        assert!(!health_report.is_live());
+
+        repository.abort_thread_worker("ProbeWorker").unwrap();
+    }
+
+    #[test]
+    async fn test_worker_mailboxes() {
+        use tokio::sync::oneshot;
+
+        enum CounterMessage {
+            Add(u32),
+            Get(oneshot::Sender<u32>),
+        }
+
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+
+        let counter_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async move {
+                let mailbox = context.mailbox::<CounterMessage>().unwrap();
+                let mut receiver = mailbox.lock().await;
+                let mut count = 0;
+
+                loop {
+                    let message = tokio::select! {
+                        biased;
+                        _ = context.cancelled() => return,
+                        message = receiver.recv() => message,
+                    };
+
+                    match message {
+                        Some(CounterMessage::Add(amount)) => count += amount,
+                        Some(CounterMessage::Get(reply_sender)) => { let _ = reply_sender.send(count); }
+                        None => return,
+                    }
+                }
+            })
+        };
+
+        // Doubles what it receives and hands it on to the counter, finding it by name
+        let relay_repository = Arc::clone(&repository);
+        let relay_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let relay_repository = Arc::clone(&relay_repository);
+            Box::pin(async move {
+                let mailbox = context.mailbox::<u32>().unwrap();
+                let counter_address = relay_repository.lock().unwrap().find_mailbox_address::<CounterMessage>("Counter").unwrap();
+
+                while let Some(amount) = mailbox.lock().await.recv().await {
+                    counter_address.send(CounterMessage::Add(amount * 2)).await.unwrap();
+                }
+            })
+        };
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            repository_guard.save_thread_worker_with_context("Counter", Box::new(counter_function));
+            repository_guard.save_thread_worker_with_context("Relay", Box::new(relay_function));
+            repository_guard.save_thread_worker("Silent", None);
+            repository_guard.attach_mailbox::<CounterMessage>("Counter", 8).unwrap();
+            repository_guard.attach_mailbox::<u32>("Relay", 1).unwrap();
+
+            assert_eq!(repository_guard.try_send("Silent", 1_u32), Err(ThreadWorkerError::NoMailbox("Silent".to_string())));
+            assert_eq!(repository_guard.try_send("Relay", "one"), Err(ThreadWorkerError::MailboxTypeMismatch("Relay".to_string())));
+
+            // Queued before the workers start; the mailbox outlives runs
+            repository_guard.try_send("Relay", 5_u32).unwrap();
+            assert_eq!(repository_guard.try_send("Relay", 6_u32), Err(ThreadWorkerError::MailboxFull("Relay".to_string())));
+
+            repository_guard.start_thread_worker("Counter").unwrap();
+            repository_guard.start_thread_worker("Relay").unwrap();
+        }
+
+        let relay_address = repository.lock().unwrap().find_mailbox_address::<u32>("Relay").unwrap();
+        relay_address.send(10).await.unwrap();
+
+        let counter_address = repository.lock().unwrap().find_mailbox_address::<CounterMessage>("Counter").unwrap();
+        // Both relayed amounts have arrived once the count reaches 30
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while counter_address.request(CounterMessage::Get).await.unwrap() < 30 {
+                tokio::task::yield_now().await;
+            }
+        }).await.expect("the relay never got both amounts to the counter");
+        assert_eq!(counter_address.request(CounterMessage::Get).await, Ok(30));
+
+        let counter_handle = repository.lock().unwrap().find_worker_handle("Counter").unwrap();
+        repository.lock().unwrap().stop_thread_worker("Counter").unwrap();
+        assert_eq!(counter_handle.join().await, WorkerExitStatus::Completed);
+
+        // Nobody answers while the counter is down, but the message waits for its next run
+        counter_address.try_send(CounterMessage::Add(1)).unwrap();
+        let counter_handle = repository.lock().unwrap().start_thread_worker("Counter").unwrap();
+        assert_eq!(counter_address.request(CounterMessage::Get).await, Ok(1));
+
+        repository.lock().unwrap().abort_thread_worker("Relay").unwrap();
+        repository.lock().unwrap().stop_thread_worker("Counter").unwrap();
+        counter_handle.join().await;
+    }
+
+    #[test]
+    async fn test_replacing_worker_group_keeps_running_workers() {
+        use crate::thread_control::entity::worker_group_kind::WorkerGroupKind;
+        use std::time::Duration;
+
+        for worker_group_kind in [WorkerGroupKind::TokioRuntime, WorkerGroupKind::ThreadPool] {
+            let mut repository = ThreadWorkerRepositoryImpl::new();
+            repository.save_worker_group(WorkerGroupConfig::new("Replaced", worker_group_kind)).unwrap();
+
+            let (release_sender, _) = tokio::sync::broadcast::channel::<()>(1);
+            let worker_release_sender = release_sender.clone();
+            let waiting_function = move |_context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+                let mut release_receiver = worker_release_sender.subscribe();
+                Box::pin(async move {
+                    let _ = release_receiver.recv().await;
+                })
+            };
+            repository.save_thread_worker_with_context("GroupedWorker", Box::new(waiting_function));
+            repository.assign_worker_group("GroupedWorker", "Replaced").unwrap();
+
+            let worker_handle = repository.start_thread_worker("GroupedWorker").unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+
+            repository.save_worker_group(WorkerGroupConfig::new("Replaced", worker_group_kind)).unwrap();
+            release_sender.send(()).unwrap();
+
+            assert_eq!(worker_handle.join_timeout(Duration::from_secs(5)).await, Ok(WorkerExitStatus::Completed));
+            assert_eq!(repository.find_by_name("GroupedWorker").unwrap().state(), ThreadWorkerState::Finished);
+
+            // The next run lands on the replacement group
+            let worker_handle = repository.start_thread_worker("GroupedWorker").unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            release_sender.send(()).unwrap();
+            assert_eq!(worker_handle.join_timeout(Duration::from_secs(5)).await, Ok(WorkerExitStatus::Completed));
+        }
     }
 }
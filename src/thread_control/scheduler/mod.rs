@@ -0,0 +1 @@
+pub mod thread_worker_scheduler;
\ No newline at end of file
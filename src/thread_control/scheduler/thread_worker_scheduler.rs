@@ -0,0 +1,210 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+use tokio::time::{interval, sleep};
+use crate::thread_control::entity::cron_expression::CronExpression;
+use crate::thread_control::entity::missed_tick_policy::MissedTickPolicy;
+use crate::thread_control::entity::thread_worker::{ThreadWorkerContextFunction, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::entity::worker_schedule::{ScheduleTrigger, WorkerSchedule};
+
+type SharedScheduledFunction = Arc<Mutex<ThreadWorkerContextFunction>>;
+
+pub struct ThreadWorkerScheduler;
+
+impl ThreadWorkerScheduler {
+    // Wraps a single-run function into one that keeps running it on the given schedule
+    // until the worker's cancellation token fires (or, for one-shot schedules, once).
+    pub fn schedule(
+        schedule: WorkerSchedule,
+        will_be_execute_function: ThreadWorkerContextFunction,
+    ) -> ThreadWorkerContextFunction {
+        let shared_function: SharedScheduledFunction = Arc::new(Mutex::new(will_be_execute_function));
+
+        Box::new(move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let schedule = schedule.clone();
+            let shared_function = Arc::clone(&shared_function);
+            Box::pin(async move {
+                ThreadWorkerScheduler::run_schedule(schedule, shared_function, context).await;
+            })
+        })
+    }
+
+    async fn run_schedule(schedule: WorkerSchedule, shared_function: SharedScheduledFunction, context: ThreadWorkerContext) {
+        match schedule.trigger() {
+            ScheduleTrigger::FixedInterval(period) => {
+                let mut ticker = interval(*period);
+                ticker.set_missed_tick_behavior(schedule.missed_tick_policy().to_missed_tick_behavior());
+
+                loop {
+                    // Checking cancellation first keeps a backlog of missed ticks from outliving a stop request
+                    tokio::select! {
+                        biased;
+                        _ = context.cancelled() => return,
+                        _ = ticker.tick() => {}
+                    }
+
+                    run_once(&shared_function, &context).await;
+                }
+            }
+            ScheduleTrigger::FixedDelay(delay) => loop {
+                run_once(&shared_function, &context).await;
+
+                if !sleep_unless_cancelled(*delay, &context).await {
+                    return;
+                }
+            },
+            ScheduleTrigger::OneShotAt(time) => {
+                if sleep_unless_cancelled(until(*time), &context).await {
+                    run_once(&shared_function, &context).await;
+                }
+            }
+            ScheduleTrigger::Cron(cron) => {
+                let mut next_run = cron.next_after(SystemTime::now());
+
+                while let Some(scheduled_at) = next_run {
+                    if !sleep_unless_cancelled(until(scheduled_at), &context).await {
+                        return;
+                    }
+
+                    run_once(&shared_function, &context).await;
+                    next_run = next_cron_run(cron, scheduled_at, schedule.missed_tick_policy());
+                }
+            }
+        }
+    }
+}
+
+async fn run_once(shared_function: &SharedScheduledFunction, context: &ThreadWorkerContext) {
+    let future = {
+        let guard = shared_function.lock().unwrap_or_else(PoisonError::into_inner);
+        (*guard)(context.clone())
+    };
+
+    future.await;
+}
+
+async fn sleep_unless_cancelled(duration: Duration, context: &ThreadWorkerContext) -> bool {
+    tokio::select! {
+        biased;
+        _ = context.cancelled() => false,
+        _ = sleep(duration) => true,
+    }
+}
+
+fn until(time: SystemTime) -> Duration {
+    time.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO)
+}
+
+fn next_cron_run(cron: &CronExpression, scheduled_at: SystemTime, missed_tick_policy: MissedTickPolicy) -> Option<SystemTime> {
+    let now = SystemTime::now();
+
+    match missed_tick_policy {
+        // Occurrences already in the past come back immediately, one after another
+        MissedTickPolicy::Burst => cron.next_after(scheduled_at),
+        MissedTickPolicy::Skip => cron.next_after(now),
+        MissedTickPolicy::Delay => match cron.next_after(scheduled_at) {
+            Some(missed_at) if missed_at <= now => Some(now),
+            _ => cron.next_after(now),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio_util::sync::CancellationToken;
+
+    fn counting_function(run_count: Arc<AtomicUsize>, first_run_time: Duration, run_time: Duration) -> ThreadWorkerContextFunction {
+        Box::new(move |_context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let run_count = Arc::clone(&run_count);
+            Box::pin(async move {
+                let previous_run_count = run_count.fetch_add(1, Ordering::SeqCst);
+                sleep(if previous_run_count == 0 { first_run_time } else { run_time }).await;
+            })
+        })
+    }
+
+    async fn run_for(schedule: WorkerSchedule, run_time: Duration, duration: Duration) -> usize {
+        run_with_slow_start_for(schedule, run_time, run_time, duration).await
+    }
+
+    async fn run_with_slow_start_for(schedule: WorkerSchedule, first_run_time: Duration, run_time: Duration, duration: Duration) -> usize {
+        let run_count = Arc::new(AtomicUsize::new(0));
+        let will_be_execute_function = counting_function(Arc::clone(&run_count), first_run_time, run_time);
+        let scheduled_function = ThreadWorkerScheduler::schedule(schedule, will_be_execute_function);
+
+        let cancellation_token = CancellationToken::new();
+        let future = scheduled_function(ThreadWorkerContext::new("ScheduledWorker", cancellation_token.clone()));
+        let task = tokio::spawn(future);
+
+        sleep(duration).await;
+        cancellation_token.cancel();
+        task.await.unwrap();
+
+        run_count.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fixed_interval() {
+        let schedule = WorkerSchedule::fixed_interval(Duration::from_millis(10)).unwrap();
+
+        // Ticks at 0, 10, 20 and 30ms
+        assert_eq!(run_for(schedule, Duration::ZERO, Duration::from_millis(35)).await, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fixed_delay() {
+        let schedule = WorkerSchedule::fixed_delay(Duration::from_millis(10));
+
+        // Each 5ms run is followed by a 10ms pause: runs start at 0, 15, 30 and 45ms
+        assert_eq!(run_for(schedule, Duration::from_millis(5), Duration::from_millis(50)).await, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fixed_interval_missed_ticks() {
+        let first_run_time = Duration::from_millis(35);
+        let window = Duration::from_millis(52);
+        let period = Duration::from_millis(10);
+
+        // The first run overruns the 10, 20 and 30ms ticks. Burst replays all of them at 35ms
+        // before continuing at 40 and 50ms, skip fires once at 35ms and rejoins the 40/50ms grid,
+        // and delay fires at 35ms and then keeps a 10ms gap from there (45ms).
+        let burst = WorkerSchedule::fixed_interval(period).unwrap().with_missed_tick_policy(MissedTickPolicy::Burst);
+        let skip = WorkerSchedule::fixed_interval(period).unwrap().with_missed_tick_policy(MissedTickPolicy::Skip);
+        let delay = WorkerSchedule::fixed_interval(period).unwrap().with_missed_tick_policy(MissedTickPolicy::Delay);
+
+        assert_eq!(run_with_slow_start_for(burst, first_run_time, Duration::ZERO, window).await, 6);
+        assert_eq!(run_with_slow_start_for(skip, first_run_time, Duration::ZERO, window).await, 4);
+        assert_eq!(run_with_slow_start_for(delay, first_run_time, Duration::ZERO, window).await, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_one_shot_at() {
+        let schedule = WorkerSchedule::one_shot_at(SystemTime::now());
+
+        assert_eq!(run_for(schedule, Duration::ZERO, Duration::from_millis(50)).await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancelled_before_cron_tick() {
+        let schedule = WorkerSchedule::cron("0 0 1 1 *").unwrap();
+
+        assert_eq!(run_for(schedule, Duration::ZERO, Duration::from_millis(50)).await, 0);
+    }
+
+    #[test]
+    fn test_next_cron_run_missed_tick_policy() {
+        let cron = CronExpression::parse("* * * * *").unwrap();
+        let long_ago = SystemTime::now() - Duration::from_secs(3600);
+
+        let burst = next_cron_run(&cron, long_ago, MissedTickPolicy::Burst).unwrap();
+        assert!(burst < SystemTime::now() - Duration::from_secs(3000));
+
+        let delay = next_cron_run(&cron, long_ago, MissedTickPolicy::Delay).unwrap();
+        assert!(delay <= SystemTime::now());
+
+        let skip = next_cron_run(&cron, long_ago, MissedTickPolicy::Skip).unwrap();
+        assert!(skip > SystemTime::now());
+    }
+}
@@ -1,12 +1,49 @@
+use std::time::Duration;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
+use tokio::sync::oneshot;
+use crate::thread_control::entity::health_report::HealthReport;
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::entity::worker_group_config::WorkerGroupConfig;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+
+pub type ThreadWorkerServiceFunction = Arc<Mutex<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>>;
+pub type SyncThreadWorkerServiceFunction = Arc<Mutex<dyn Fn() + Send>>;
+pub type ThreadWorkerServiceContextFunction = Arc<Mutex<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>>;
+pub type ThreadWorkerServiceOutputFunction<T> = Arc<Mutex<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = T> + Send>> + Send>>;
+pub type LocalThreadWorkerServiceFunction = Arc<Mutex<dyn Fn(ThreadWorkerContext) -> Pin<Box<dyn Future<Output = ()>>> + Send>>;
 
 #[async_trait]
 pub trait ThreadWorkerServiceTrait {
-    fn save_async_thread_worker(&mut self, name: &str, will_be_execute_function: Arc<Mutex<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>);
-    fn save_sync_thread_worker(&mut self, name: &str, will_be_execute_function: Arc<Mutex<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>);
-    async fn start_thread_worker(&self, name: &str);
+    fn save_async_thread_worker(&mut self, name: &str, will_be_execute_function: ThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError>;
+    fn save_sync_thread_worker(&mut self, name: &str, will_be_execute_function: SyncThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError>;
+    fn save_async_thread_worker_with_context(&mut self, name: &str, will_be_execute_context_function: ThreadWorkerServiceContextFunction) -> Result<(), ThreadWorkerError>;
+    fn save_local_thread_worker(&mut self, name: &str, will_be_execute_local_function: LocalThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError>;
+    fn save_thread_worker_with_output<T: Send + 'static>(&mut self, name: &str, will_be_execute_output_function: ThreadWorkerServiceOutputFunction<T>) -> Result<(), ThreadWorkerError>;
+    fn take_output<T: 'static>(&self, name: &str) -> Result<T, ThreadWorkerError>;
+    fn save_worker_group(&mut self, worker_group_config: WorkerGroupConfig) -> Result<(), ThreadWorkerError>;
+    fn assign_worker_group(&mut self, name: &str, worker_group_name: &str) -> Result<(), ThreadWorkerError>;
+    fn add_dependency(&mut self, name: &str, dependency_name: &str) -> Result<(), ThreadWorkerError>;
+    fn require_readiness_signal(&mut self, name: &str) -> Result<(), ThreadWorkerError>;
+    fn set_liveness_timeout(&mut self, name: &str, liveness_timeout: Duration) -> Result<(), ThreadWorkerError>;
+    fn start_thread_worker(&self, name: &str) -> Result<WorkerHandle, ThreadWorkerError>;
+    fn stop_thread_worker(&self, name: &str) -> Result<(), ThreadWorkerError>;
+    fn get_health_report(&self) -> Result<HealthReport, ThreadWorkerError>;
+    fn attach_mailbox<M: Send + 'static>(&self, name: &str, capacity: usize) -> Result<(), ThreadWorkerError>;
+    async fn send<M: Send + 'static>(&self, name: &str, message: M) -> Result<(), ThreadWorkerError>;
+    fn try_send<M: Send + 'static>(&self, name: &str, message: M) -> Result<(), ThreadWorkerError>;
+    async fn request<M, R, B>(&self, name: &str, build_message: B) -> Result<R, ThreadWorkerError>
+    where
+        M: Send + 'static,
+        R: Send + 'static,
+        B: FnOnce(oneshot::Sender<R>) -> M + Send + 'static;
+    async fn request_with_timeout<M, R, B>(&self, name: &str, build_message: B, request_timeout: Duration) -> Result<R, ThreadWorkerError>
+    where
+        M: Send + 'static,
+        R: Send + 'static,
+        B: FnOnce(oneshot::Sender<R>) -> M + Send + 'static;
 }
 
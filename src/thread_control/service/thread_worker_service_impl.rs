@@ -1,13 +1,20 @@
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 use async_trait::async_trait;
 use lazy_static::lazy_static;
-use tokio::runtime::Handle;
-use tokio::task;
+use tokio::sync::oneshot;
+use crate::thread_control::entity::health_report::HealthReport;
+use crate::thread_control::entity::mailbox_address::MailboxAddress;
+use crate::thread_control::entity::thread_worker::{LocalThreadWorkerFuture, ThreadWorkerFuture};
+use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+use crate::thread_control::entity::worker_group_config::WorkerGroupConfig;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
 use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
 use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
-use crate::thread_control::service::thread_worker_service::ThreadWorkerServiceTrait;
+use crate::thread_control::service::thread_worker_service::{LocalThreadWorkerServiceFunction, SyncThreadWorkerServiceFunction, ThreadWorkerServiceContextFunction, ThreadWorkerServiceFunction, ThreadWorkerServiceOutputFunction, ThreadWorkerServiceTrait};
 
 pub struct ThreadWorkerServiceImpl {
     repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
@@ -25,72 +32,173 @@ impl ThreadWorkerServiceImpl {
         }
         INSTANCE.clone()
     }
+
+    // The address is looked up under the lock and used after it is released, so waiting never blocks the repository
+    fn find_mailbox_address<M: Send + 'static>(&self, name: &str) -> Result<MailboxAddress<M>, ThreadWorkerError> {
+        self.repository.lock()?.find_mailbox_address(name)
+    }
+}
+
+// Calling the function cannot leave it half-updated, so a poisoned lock is still safe to use
+fn lock_function<F: ?Sized>(function: &Mutex<F>) -> MutexGuard<'_, F> {
+    function.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 #[async_trait]
 impl ThreadWorkerServiceTrait for ThreadWorkerServiceImpl {
-    fn save_async_thread_worker(&mut self, name: &str, will_be_execute_function: Arc<Mutex<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>) {
-        let async_function = move || -> Pin<Box<dyn Future<Output = ()>>> {
+    fn save_async_thread_worker(&mut self, name: &str, will_be_execute_function: ThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError> {
+        let async_function = move || -> ThreadWorkerFuture {
             let will_be_execute_function = Arc::clone(&will_be_execute_function);
             Box::pin(async move {
-                (will_be_execute_function.lock().unwrap())().await
+                let future = (lock_function(&will_be_execute_function))();
+                future.await
             })
         };
 
-        self.repository.lock().unwrap().save_thread_worker(name, Some(Box::new(async_function)));
+        self.repository.lock()?.save_thread_worker(name, Some(Box::new(async_function)));
+        Ok(())
     }
 
-    fn save_sync_thread_worker(&mut self, name: &str, will_be_execute_function: Arc<Mutex<dyn Fn() -> Pin<Box<dyn Future<Output = ()>>> + Send>>) {
-        let sync_function = move || -> Pin<Box<dyn Future<Output = ()>>> {
-            let will_be_execute_function = Arc::clone(&will_be_execute_function);
+    // Runs on tokio's blocking pool, so blocking file or CPU work never holds up the async runtime
+    fn save_sync_thread_worker(&mut self, name: &str, will_be_execute_function: SyncThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError> {
+        let blocking_function = move |_context: ThreadWorkerContext| {
+            (lock_function(&will_be_execute_function))()
+        };
+
+        self.repository.lock()?.save_blocking_thread_worker(name, Box::new(blocking_function));
+        Ok(())
+    }
+
+    fn save_async_thread_worker_with_context(&mut self, name: &str, will_be_execute_context_function: ThreadWorkerServiceContextFunction) -> Result<(), ThreadWorkerError> {
+        let async_context_function = move |context: ThreadWorkerContext| -> ThreadWorkerFuture {
+            let will_be_execute_context_function = Arc::clone(&will_be_execute_context_function);
             Box::pin(async move {
-                (will_be_execute_function.lock().unwrap())().await
+                let future = (lock_function(&will_be_execute_context_function))(context);
+                future.await
             })
         };
 
-        self.repository.lock().unwrap().save_thread_worker(name, Some(Box::new(sync_function)));
+        self.repository.lock()?.save_thread_worker_with_context(name, Box::new(async_context_function));
+        Ok(())
     }
 
-    async fn start_thread_worker(&self, name: &str) {
-        let repository_lock = self.repository.lock().unwrap();
+    fn save_local_thread_worker(&mut self, name: &str, will_be_execute_local_function: LocalThreadWorkerServiceFunction) -> Result<(), ThreadWorkerError> {
+        let local_function = move |context: ThreadWorkerContext| -> LocalThreadWorkerFuture {
+            let will_be_execute_local_function = Arc::clone(&will_be_execute_local_function);
+            Box::pin(async move {
+                let future = (lock_function(&will_be_execute_local_function))(context);
+                future.await
+            })
+        };
 
-        task::block_in_place(move || {
-            Handle::current().block_on(async move {
-                repository_lock.start_thread_worker(name).await;
-            });
-        });
+        self.repository.lock()?.save_local_thread_worker(name, Box::new(local_function));
+        Ok(())
+    }
+
+    fn save_thread_worker_with_output<T: Send + 'static>(&mut self, name: &str, will_be_execute_output_function: ThreadWorkerServiceOutputFunction<T>) -> Result<(), ThreadWorkerError> {
+        let output_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = T> + Send>> {
+            let will_be_execute_output_function = Arc::clone(&will_be_execute_output_function);
+            Box::pin(async move {
+                let future = (lock_function(&will_be_execute_output_function))(context);
+                future.await
+            })
+        };
+
+        self.repository.lock()?.save_thread_worker_with_output(name, Box::new(output_function));
+        Ok(())
+    }
+
+    fn take_output<T: 'static>(&self, name: &str) -> Result<T, ThreadWorkerError> {
+        self.repository.lock()?.take_output(name)
+    }
+
+    fn save_worker_group(&mut self, worker_group_config: WorkerGroupConfig) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.save_worker_group(worker_group_config)
+    }
+
+    fn assign_worker_group(&mut self, name: &str, worker_group_name: &str) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.assign_worker_group(name, worker_group_name)
+    }
+
+    fn add_dependency(&mut self, name: &str, dependency_name: &str) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.add_dependency(name, dependency_name)
+    }
+
+    fn require_readiness_signal(&mut self, name: &str) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.require_readiness_signal(name)
+    }
+
+    fn set_liveness_timeout(&mut self, name: &str, liveness_timeout: Duration) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.set_liveness_timeout(name, liveness_timeout)
+    }
+
+    fn start_thread_worker(&self, name: &str) -> Result<WorkerHandle, ThreadWorkerError> {
+        self.repository.lock()?.start_thread_worker(name)
+    }
+
+    fn stop_thread_worker(&self, name: &str) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.stop_thread_worker(name)
+    }
+
+    fn get_health_report(&self) -> Result<HealthReport, ThreadWorkerError> {
+        Ok(self.repository.lock()?.get_health_report())
+    }
+
+    fn attach_mailbox<M: Send + 'static>(&self, name: &str, capacity: usize) -> Result<(), ThreadWorkerError> {
+        self.repository.lock()?.attach_mailbox::<M>(name, capacity)
+    }
+
+    async fn send<M: Send + 'static>(&self, name: &str, message: M) -> Result<(), ThreadWorkerError> {
+        self.find_mailbox_address(name)?.send(message).await
+    }
+
+    fn try_send<M: Send + 'static>(&self, name: &str, message: M) -> Result<(), ThreadWorkerError> {
+        self.find_mailbox_address(name)?.try_send(message)
+    }
+
+    async fn request<M, R, B>(&self, name: &str, build_message: B) -> Result<R, ThreadWorkerError>
+    where
+        M: Send + 'static,
+        R: Send + 'static,
+        B: FnOnce(oneshot::Sender<R>) -> M + Send + 'static,
+    {
+        self.find_mailbox_address(name)?.request(build_message).await
+    }
+
+    async fn request_with_timeout<M, R, B>(&self, name: &str, build_message: B, request_timeout: Duration) -> Result<R, ThreadWorkerError>
+    where
+        M: Send + 'static,
+        R: Send + 'static,
+        B: FnOnce(oneshot::Sender<R>) -> M + Send + 'static,
+    {
+        self.find_mailbox_address(name)?.request_with_timeout(build_message, request_timeout).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
     use tokio::test;
 
-    fn my_sync_function() {
-        println!("Synchronous function is executed!");
-    }
-
-    async fn my_async_function() {
-        println!("Asynchronous function is executed!");
-    }
-
     #[test]
     async fn test_save_async_thread_worker() {
         let thread_worker_repository = ThreadWorkerRepositoryImpl::get_instance();
         let mut service = ThreadWorkerServiceImpl::new(thread_worker_repository);
         // let mut service = ThreadWorkerServiceImpl::get_instance();
 
-        let async_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let async_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 println!("Custom async function executed!");
             })
         };
 
-        service.save_async_thread_worker("AsyncTestWorker", Arc::new(Mutex::new(async_function)));
+        service.save_async_thread_worker("AsyncTestWorker", Arc::new(Mutex::new(async_function))).unwrap();
 
         // Retrieve the saved worker and execute its function
-        if let Some(worker) = service.repository.lock().unwrap().find_by_name("AsyncTestWorker") {
+        let saved_worker = service.repository.lock().unwrap().find_by_name("AsyncTestWorker");
+        if let Some(worker) = saved_worker {
             let function_arc = Arc::clone(&worker.get_will_be_execute_function().unwrap());
 
             // Lock the Mutex to get the guard
@@ -115,33 +223,16 @@ mod tests {
         let repository = ThreadWorkerRepositoryImpl::get_instance();
         let mut service = ThreadWorkerServiceImpl::new(repository);
 
-        let sync_function = || -> Pin<Box<dyn Future<Output = ()>>> {
-            Box::pin(async {
-                println!("Custom sync function executed!");
-            })
+        let sync_function = || {
+            println!("Custom sync function executed!");
         };
 
-        service.save_sync_thread_worker("SyncTestWorker", Arc::new(Mutex::new(sync_function)));
-
-        // Retrieve the saved worker and execute its function
-        if let Some(worker) = service.repository.lock().unwrap().find_by_name("SyncTestWorker") {
-            let function_arc = Arc::clone(&worker.get_will_be_execute_function().unwrap());
-
-            // Lock the Mutex to get the guard
-            let guard = function_arc.lock().await;
-
-            // Extract the closure from the Box inside the Mutex guard
-            let function = &*guard;
-
-            // Call the closure and execute the future
-            let future = function();
-            future.await;
+        service.save_sync_thread_worker("SyncTestWorker", Arc::new(Mutex::new(sync_function))).unwrap();
 
-            // Add an assertion to check if the worker name matches
-            assert_eq!(worker.name(), "SyncTestWorker");
-        } else {
-            panic!("Thread worker not found: SyncTestWorker");
-        };
+        let saved_worker = service.repository.lock().unwrap().find_by_name("SyncTestWorker").unwrap();
+        assert_eq!(saved_worker.name(), "SyncTestWorker");
+        assert!(saved_worker.is_blocking());
+        assert!(saved_worker.has_function());
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -150,28 +241,221 @@ mod tests {
         let mut service = ThreadWorkerServiceImpl::new(thread_worker_repository);
         // let mut service = ThreadWorkerServiceImpl::get_instance();
 
-        let async_function = || -> Pin<Box<dyn Future<Output = ()>>> {
+        let async_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Box::pin(async {
                 println!("Custom async function executed!");
             })
         };
 
-        service.save_async_thread_worker("AsyncTestWorker", Arc::new(Mutex::new(async_function)));
-        service.start_thread_worker("AsyncTestWorker").await;
+        service.save_async_thread_worker("AsyncStartTestWorker", Arc::new(Mutex::new(async_function))).unwrap();
+        let worker_handle = service.start_thread_worker("AsyncStartTestWorker").unwrap();
+
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    #[tokio::test]
     async fn test_save_sync_thread_and_start() {
+        use std::time::Duration;
+
         let repository = ThreadWorkerRepositoryImpl::get_instance();
         let mut service = ThreadWorkerServiceImpl::new(repository);
 
-        let sync_function = || -> Pin<Box<dyn Future<Output = ()>>> {
-            Box::pin(async {
-                println!("Custom sync function executed!");
+        // Blocks its thread until released; on the single-threaded test runtime that only works
+        // if the worker runs somewhere else while the runtime keeps going
+        let (started_sender, mut started_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (release_sender, release_receiver) = std::sync::mpsc::channel::<()>();
+        let sync_function = move || {
+            started_sender.send(()).unwrap();
+            release_receiver.recv().unwrap();
+        };
+
+        service.save_sync_thread_worker("SyncStartTestWorker", Arc::new(Mutex::new(sync_function))).unwrap();
+        let worker_handle = service.start_thread_worker("SyncStartTestWorker").unwrap();
+
+        let started = tokio::time::timeout(Duration::from_secs(5), started_receiver.recv()).await;
+        assert_eq!(started, Ok(Some(())));
+        assert!(!worker_handle.is_finished());
+
+        release_sender.send(()).unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_start_missing_thread_worker() {
+        let service = ThreadWorkerServiceImpl::new(Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new())));
+
+        assert_eq!(
+            service.start_thread_worker("MissingServiceWorker").unwrap_err(),
+            ThreadWorkerError::NotFound("MissingServiceWorker".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_poisoned_repository_lock() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let poisoned_repository = Arc::clone(&repository);
+
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoned_repository.lock().unwrap();
+            panic!("Poison the repository lock");
+        }).join();
+
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        let async_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async {})
+        };
+
+        assert!(matches!(
+            service.save_async_thread_worker("PoisonedWorker", Arc::new(Mutex::new(async_function))),
+            Err(ThreadWorkerError::LockPoisoned(_))
+        ));
+        assert!(matches!(
+            service.start_thread_worker("PoisonedWorker"),
+            Err(ThreadWorkerError::LockPoisoned(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_save_async_thread_worker_with_context_and_stop() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        let loop_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async move {
+                while !context.is_cancelled() {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+                println!("{} left its loop", context.name());
             })
         };
 
-        service.save_sync_thread_worker("SyncTestWorker", Arc::new(Mutex::new(sync_function)));
-        service.start_thread_worker("SyncTestWorker").await;
+        service.save_async_thread_worker_with_context("ContextLoopWorker", Arc::new(Mutex::new(loop_function))).unwrap();
+
+        let worker_handle = service.start_thread_worker("ContextLoopWorker").unwrap();
+        service.stop_thread_worker("ContextLoopWorker").unwrap();
+
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_health_report() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        let ready_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async move {
+                context.mark_ready();
+                context.cancelled().await;
+            })
+        };
+
+        service.save_async_thread_worker_with_context("HealthServiceWorker", Arc::new(Mutex::new(ready_function))).unwrap();
+        service.require_readiness_signal("HealthServiceWorker").unwrap();
+        let health_report = service.get_health_report().unwrap();
+        assert_eq!(health_report.find_by_name("HealthServiceWorker").unwrap().get_state(), ThreadWorkerState::Registered);
+
+        let worker_handle = service.start_thread_worker("HealthServiceWorker").unwrap();
+        worker_handle.wait_ready().await.unwrap();
+        assert!(service.get_health_report().unwrap().is_ready());
+
+        service.stop_thread_worker("HealthServiceWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    // An amount to add, with somewhere to send the new sum when the sender wants it
+    type SumMessage = (u32, Option<oneshot::Sender<u32>>);
+
+    #[tokio::test]
+    async fn test_worker_mailbox_through_service() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        // Answers every question with the sum of what it was sent so far
+        let summing_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            Box::pin(async move {
+                let mailbox = context.mailbox::<SumMessage>().unwrap();
+                let mut receiver = mailbox.lock().await;
+                let mut sum = 0;
+
+                loop {
+                    let message = tokio::select! {
+                        _ = context.cancelled() => return,
+                        message = receiver.recv() => message,
+                    };
+
+                    let Some((amount, reply_sender)) = message else {
+                        return;
+                    };
+
+                    sum += amount;
+                    if let Some(reply_sender) = reply_sender {
+                        let _ = reply_sender.send(sum);
+                    }
+                }
+            })
+        };
+
+        service.save_async_thread_worker_with_context("SummingWorker", Arc::new(Mutex::new(summing_function))).unwrap();
+        service.attach_mailbox::<SumMessage>("SummingWorker", 1).unwrap();
+
+        service.try_send::<SumMessage>("SummingWorker", (1, None)).unwrap();
+        assert_eq!(
+            service.try_send::<SumMessage>("SummingWorker", (2, None)),
+            Err(ThreadWorkerError::MailboxFull("SummingWorker".to_string()))
+        );
+        assert_eq!(service.try_send("SummingWorker", 3_u32), Err(ThreadWorkerError::MailboxTypeMismatch("SummingWorker".to_string())));
+
+        let worker_handle = service.start_thread_worker("SummingWorker").unwrap();
+        service.send::<SumMessage>("SummingWorker", (2, None)).await.unwrap();
+        assert_eq!(service.request::<SumMessage, u32, _>("SummingWorker", |reply_sender| (3, Some(reply_sender))).await, Ok(6));
+        assert_eq!(
+            service.request_with_timeout::<SumMessage, u32, _>("SummingWorker", |reply_sender| (4, Some(reply_sender)), Duration::from_secs(5)).await,
+            Ok(10)
+        );
+
+        // The repository stays usable while a request is waiting for its answer
+        assert!(service.get_health_report().unwrap().find_by_name("SummingWorker").is_some());
+
+        service.stop_thread_worker("SummingWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_save_thread_worker_with_output() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        let length_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = usize> + Send>> {
+            Box::pin(async move { context.name().len() })
+        };
+
+        service.save_thread_worker_with_output("LengthWorker", Arc::new(Mutex::new(length_function))).unwrap();
+        assert_eq!(service.take_output::<String>("LengthWorker"), Err(ThreadWorkerError::OutputTypeMismatch("LengthWorker".to_string())));
+
+        let worker_handle = service.start_thread_worker("LengthWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        assert_eq!(service.take_output::<usize>("LengthWorker"), Ok(12));
+        assert_eq!(service.take_output::<usize>("LengthWorker"), Err(ThreadWorkerError::NoOutput("LengthWorker".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_save_local_thread_worker_and_start() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let mut service = ThreadWorkerServiceImpl::new(repository);
+
+        let local_function = |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()>>> {
+            let local_counter = std::rc::Rc::new(std::cell::Cell::new(0));
+            Box::pin(async move {
+                local_counter.set(local_counter.get() + 1);
+                tokio::task::yield_now().await;
+                println!("{} counted {}", context.name(), local_counter.get());
+            })
+        };
+
+        service.save_local_thread_worker("LocalServiceWorker", Arc::new(Mutex::new(local_function))).unwrap();
+
+        let worker_handle = service.start_thread_worker("LocalServiceWorker").unwrap();
+        assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod shutdown_coordinator;
+pub mod shutdown_report;
\ No newline at end of file
@@ -0,0 +1,193 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+use crate::thread_control::shutdown::shutdown_report::ShutdownReport;
+
+const DEFAULT_SHUTDOWN_DEADLINE: Duration = Duration::from_secs(5);
+const DEFAULT_ABORT_GRACE: Duration = Duration::from_millis(500);
+
+pub struct ShutdownCoordinator {
+    repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    deadline: Duration,
+    abort_grace: Duration,
+}
+
+impl ShutdownCoordinator {
+    pub fn new(repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Self {
+        ShutdownCoordinator {
+            repository,
+            deadline: DEFAULT_SHUTDOWN_DEADLINE,
+            abort_grace: DEFAULT_ABORT_GRACE,
+        }
+    }
+
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn with_abort_grace(mut self, abort_grace: Duration) -> Self {
+        self.abort_grace = abort_grace;
+        self
+    }
+
+    // Workers tied together by dependencies are stopped one at a time, dependents first,
+    // before the shutdown token brings down everything else at once
+    pub async fn shutdown(&self) -> Result<ShutdownReport, ThreadWorkerError> {
+        let deadline = Instant::now() + self.deadline;
+        let mut report = ShutdownReport::new();
+        let mut straggler_list = Vec::new();
+
+        let ordered_name_list = self.repository.lock()?.find_dependency_shutdown_order();
+
+        for name in &ordered_name_list {
+            let worker_handle = {
+                let mut repository = self.repository.lock()?;
+                repository.stop_thread_worker(name)?;
+                repository.find_worker_handle(name)
+            };
+
+            if let Some(worker_handle) = worker_handle {
+                join_before(deadline, worker_handle, &mut report, &mut straggler_list).await;
+            }
+        }
+
+        let worker_handle_list = self.repository.lock()?.begin_shutdown();
+
+        for worker_handle in worker_handle_list {
+            if !ordered_name_list.iter().any(|name| name == worker_handle.name()) {
+                join_before(deadline, worker_handle, &mut report, &mut straggler_list).await;
+            }
+        }
+
+        for worker_handle in &straggler_list {
+            worker_handle.abort();
+        }
+
+        // An aborted worker only stops at its next await point, so one stuck in blocking code may never report back
+        let abort_deadline = Instant::now() + self.abort_grace;
+
+        for worker_handle in straggler_list {
+            match timeout_at(abort_deadline, worker_handle.join()).await {
+                Ok(_) => report.record_aborted(worker_handle.name()),
+                Err(_) => report.record_unresponsive(worker_handle.name()),
+            }
+        }
+
+        Ok(report)
+    }
+
+    // Without any signal to wait for nothing is shut down, and the caller decides what to do instead
+    pub async fn shutdown_on_signal(&self) -> Result<ShutdownReport, ThreadWorkerError> {
+        wait_for_shutdown_signal()
+            .await
+            .map_err(|error| ThreadWorkerError::SignalUnavailable(error.to_string()))?;
+
+        self.shutdown().await
+    }
+}
+
+async fn join_before(deadline: Instant, worker_handle: WorkerHandle, report: &mut ShutdownReport, straggler_list: &mut Vec<WorkerHandle>) {
+    match timeout_at(deadline, worker_handle.join()).await {
+        Ok(exit_status @ (WorkerExitStatus::Panicked(_) | WorkerExitStatus::Failed(_))) => {
+            report.record_failed(worker_handle.name(), exit_status)
+        }
+        Ok(_) => report.record_exited(worker_handle.name()),
+        Err(_) => straggler_list.push(worker_handle),
+    }
+}
+
+// A listener that cannot be installed is left out as long as the other one still works
+#[cfg(unix)]
+pub async fn wait_for_shutdown_signal() -> io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(error) => {
+            eprintln!("Failed to listen for SIGTERM, waiting for Ctrl-C only: {}", error);
+            return tokio::signal::ctrl_c().await;
+        }
+    };
+
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            if let Err(error) = result {
+                eprintln!("Failed to listen for Ctrl-C, waiting for SIGTERM only: {}", error);
+                terminate.recv().await;
+            }
+            Ok(())
+        }
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn wait_for_shutdown_signal() -> io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+
+    #[tokio::test]
+    async fn test_shutdown_cooperative_and_stubborn_workers() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            let shutdown_token = repository_guard.get_shutdown_token();
+
+            let cooperative_function = move || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+                let shutdown_token = shutdown_token.clone();
+                Box::pin(async move {
+                    shutdown_token.cancelled().await;
+                })
+            };
+
+            let stubborn_function = || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+                Box::pin(async {
+                    loop {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                })
+            };
+
+            repository_guard.save_thread_worker("CooperativeWorker", Some(Box::new(cooperative_function)));
+            repository_guard.save_thread_worker("StubbornWorker", Some(Box::new(stubborn_function)));
+            repository_guard.start_thread_worker("CooperativeWorker").unwrap();
+            repository_guard.start_thread_worker("StubbornWorker").unwrap();
+        }
+
+        let coordinator = ShutdownCoordinator::new(Arc::clone(&repository))
+            .with_deadline(Duration::from_millis(100));
+
+        let report = coordinator.shutdown().await.unwrap();
+
+        assert_eq!(report.get_exited_worker_list(), &vec!["CooperativeWorker".to_string()]);
+        assert_eq!(report.get_aborted_worker_list(), &vec!["StubbornWorker".to_string()]);
+        assert_eq!(report.unclean_worker_names(), vec!["StubbornWorker"]);
+
+        let repository_guard = repository.lock().unwrap();
+        assert!(repository_guard.find_all_alive().is_empty());
+        assert_eq!(repository_guard.find_all_by_state(ThreadWorkerState::Finished).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_without_workers() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let coordinator = ShutdownCoordinator::new(repository);
+
+        assert!(coordinator.shutdown().await.unwrap().is_clean());
+    }
+}
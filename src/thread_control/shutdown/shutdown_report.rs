@@ -0,0 +1,91 @@
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    exited_worker_list: Vec<String>,
+    failed_worker_list: Vec<(String, WorkerExitStatus)>,
+    aborted_worker_list: Vec<String>,
+    unresponsive_worker_list: Vec<String>,
+}
+
+impl ShutdownReport {
+    pub fn new() -> Self {
+        ShutdownReport::default()
+    }
+
+    pub fn record_exited(&mut self, name: &str) {
+        self.exited_worker_list.push(name.to_string());
+    }
+
+    pub fn record_failed(&mut self, name: &str, exit_status: WorkerExitStatus) {
+        self.failed_worker_list.push((name.to_string(), exit_status));
+    }
+
+    pub fn record_aborted(&mut self, name: &str) {
+        self.aborted_worker_list.push(name.to_string());
+    }
+
+    pub fn record_unresponsive(&mut self, name: &str) {
+        self.unresponsive_worker_list.push(name.to_string());
+    }
+
+    pub fn get_exited_worker_list(&self) -> &Vec<String> {
+        &self.exited_worker_list
+    }
+
+    pub fn get_failed_worker_list(&self) -> &Vec<(String, WorkerExitStatus)> {
+        &self.failed_worker_list
+    }
+
+    pub fn get_aborted_worker_list(&self) -> &Vec<String> {
+        &self.aborted_worker_list
+    }
+
+    pub fn get_unresponsive_worker_list(&self) -> &Vec<String> {
+        &self.unresponsive_worker_list
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.failed_worker_list.is_empty()
+            && self.aborted_worker_list.is_empty()
+            && self.unresponsive_worker_list.is_empty()
+    }
+
+    pub fn unclean_worker_names(&self) -> Vec<String> {
+        let mut unclean_worker_names: Vec<String> = self.failed_worker_list
+            .iter()
+            .map(|(name, _)| name.clone())
+            .chain(self.aborted_worker_list.iter().cloned())
+            .chain(self.unresponsive_worker_list.iter().cloned())
+            .collect();
+
+        unclean_worker_names.sort();
+        unclean_worker_names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_report() {
+        let mut report = ShutdownReport::new();
+        report.record_exited("Receiver");
+
+        assert!(report.is_clean());
+        assert!(report.unclean_worker_names().is_empty());
+    }
+
+    #[test]
+    fn test_unclean_worker_names() {
+        let mut report = ShutdownReport::new();
+        report.record_exited("Receiver");
+        report.record_unresponsive("Transmitter");
+        report.record_failed("Heartbeat", WorkerExitStatus::Panicked("boom".to_string()));
+        report.record_aborted("Cleanup");
+
+        assert!(!report.is_clean());
+        assert_eq!(report.unclean_worker_names(), vec!["Cleanup", "Heartbeat", "Transmitter"]);
+    }
+}
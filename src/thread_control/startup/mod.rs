@@ -0,0 +1,2 @@
+pub mod startup_coordinator;
+pub mod startup_report;
\ No newline at end of file
@@ -0,0 +1,268 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+use crate::thread_control::startup::startup_report::StartupReport;
+
+const DEFAULT_READINESS_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct StartupCoordinator {
+    repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+    readiness_timeout: Duration,
+}
+
+impl StartupCoordinator {
+    pub fn new(repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Self {
+        StartupCoordinator {
+            repository,
+            readiness_timeout: DEFAULT_READINESS_TIMEOUT,
+        }
+    }
+
+    pub fn with_readiness_timeout(mut self, readiness_timeout: Duration) -> Self {
+        self.readiness_timeout = readiness_timeout;
+        self
+    }
+
+    // Starts every registered worker in dependency order, each one only after everything it depends on is ready.
+    // Workers registered without a function, and everything depending on them, are skipped and listed in the report.
+    // If a worker fails to start or get ready, the workers already started are stopped again, dependents first.
+    pub async fn start_all(&self) -> Result<StartupReport, ThreadWorkerError> {
+        let start_order = self.repository.lock()?.find_start_order()?;
+        let mut report = StartupReport::new();
+
+        for name in start_order {
+            if !self.can_start(&name, &report)? {
+                report.record_skipped(&name);
+                continue;
+            }
+
+            let worker_handle = match self.start(&name) {
+                Ok(worker_handle) => worker_handle,
+                Err(error) => {
+                    self.stop_started(report.get_worker_handle_list());
+                    return Err(error);
+                }
+            };
+
+            report.record_started(worker_handle.clone());
+
+            if let Err(error) = worker_handle.wait_ready_timeout(self.readiness_timeout).await {
+                self.stop_started(report.get_worker_handle_list());
+                return Err(error);
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn can_start(&self, name: &str, report: &StartupReport) -> Result<bool, ThreadWorkerError> {
+        let repository = self.repository.lock()?;
+        let worker = repository
+            .find_by_name(name)
+            .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string()))?;
+
+        Ok(worker.has_function()
+            && !worker.get_dependency_list().iter().any(|dependency_name| report.is_skipped(dependency_name)))
+    }
+
+    fn start(&self, name: &str) -> Result<WorkerHandle, ThreadWorkerError> {
+        let mut repository = self.repository.lock()?;
+
+        // A worker that is already up is waited on like one started here
+        match repository.start_thread_worker(name) {
+            Err(ThreadWorkerError::AlreadyRunning(_)) => repository
+                .find_worker_handle(name)
+                .ok_or_else(|| ThreadWorkerError::NotFound(name.to_string())),
+            start_result => start_result,
+        }
+    }
+
+    fn stop_started(&self, worker_handle_list: &[WorkerHandle]) {
+        let Ok(mut repository) = self.repository.lock() else {
+            return;
+        };
+
+        for worker_handle in worker_handle_list.iter().rev() {
+            let _ = repository.stop_thread_worker(worker_handle.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+    use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+    use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+    use crate::thread_control::shutdown::shutdown_coordinator::ShutdownCoordinator;
+
+    type EventList = Arc<Mutex<Vec<String>>>;
+
+    // Records its start, optionally reports ready after a delay, then records its stop once cancelled
+    fn save_recording_worker(repository: &mut ThreadWorkerRepositoryImpl, name: &str, ready_delay: Option<Duration>, event_list: &EventList) {
+        let event_list = Arc::clone(event_list);
+        let recording_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let event_list = Arc::clone(&event_list);
+            Box::pin(async move {
+                event_list.lock().unwrap().push(format!("start {}", context.name()));
+
+                if let Some(ready_delay) = ready_delay {
+                    if tokio::time::timeout(ready_delay, context.cancelled()).await.is_ok() {
+                        event_list.lock().unwrap().push(format!("stop {}", context.name()));
+                        return;
+                    }
+                    event_list.lock().unwrap().push(format!("ready {}", context.name()));
+                    context.mark_ready();
+                }
+
+                context.cancelled().await;
+                event_list.lock().unwrap().push(format!("stop {}", context.name()));
+            })
+        };
+
+        repository.save_thread_worker_with_context(name, Box::new(recording_function));
+
+        if ready_delay.is_some() {
+            repository.require_readiness_signal(name).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_all_in_dependency_order_and_shutdown_in_reverse() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let event_list: EventList = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            save_recording_worker(&mut repository_guard, "Transmitter", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Dispatcher", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Receiver", Some(Duration::from_millis(20)), &event_list);
+            save_recording_worker(&mut repository_guard, "Connection", Some(Duration::from_millis(20)), &event_list);
+
+            repository_guard.add_dependency("Transmitter", "Connection").unwrap();
+            repository_guard.add_dependency("Receiver", "Connection").unwrap();
+            repository_guard.add_dependency("Dispatcher", "Receiver").unwrap();
+        }
+
+        let startup_report = StartupCoordinator::new(Arc::clone(&repository)).start_all().await.unwrap();
+        let started_name_list: Vec<&str> = startup_report.get_worker_handle_list().iter().map(WorkerHandle::name).collect();
+        assert_eq!(started_name_list, vec!["Connection", "Receiver", "Dispatcher", "Transmitter"]);
+        assert!(startup_report.get_skipped_worker_list().is_empty());
+
+        // Nothing starts before what it depends on has reported ready
+        tokio::task::yield_now().await;
+        assert_eq!(
+            event_list.lock().unwrap()[..4],
+            ["start Connection", "ready Connection", "start Receiver", "ready Receiver"]
+        );
+
+        let report = ShutdownCoordinator::new(Arc::clone(&repository))
+            .with_deadline(Duration::from_secs(1))
+            .shutdown()
+            .await
+            .unwrap();
+
+        assert!(report.is_clean());
+        assert_eq!(report.get_exited_worker_list(), &vec!["Transmitter", "Dispatcher", "Receiver", "Connection"]);
+
+        let stop_list: Vec<String> = event_list.lock().unwrap()
+            .iter()
+            .filter(|event| event.starts_with("stop"))
+            .cloned()
+            .collect();
+        assert_eq!(stop_list, vec!["stop Transmitter", "stop Dispatcher", "stop Receiver", "stop Connection"]);
+    }
+
+    #[tokio::test]
+    async fn test_start_all_rejects_cycles_and_missing_dependencies() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let event_list: EventList = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            save_recording_worker(&mut repository_guard, "First", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Second", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Third", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Independent", None, &event_list);
+
+            repository_guard.add_dependency("First", "Second").unwrap();
+            repository_guard.add_dependency("Second", "First").unwrap();
+            repository_guard.add_dependency("Third", "Second").unwrap();
+            assert_eq!(
+                repository_guard.add_dependency("First", "First"),
+                Err(ThreadWorkerError::DependencyCycle("First".to_string()))
+            );
+        }
+
+        let coordinator = StartupCoordinator::new(Arc::clone(&repository));
+        assert_eq!(
+            coordinator.start_all().await.unwrap_err(),
+            ThreadWorkerError::DependencyCycle("First, Second, Third".to_string())
+        );
+        assert!(repository.lock().unwrap().find_all_alive().is_empty());
+
+        save_recording_worker(&mut repository.lock().unwrap(), "First", None, &event_list);
+        repository.lock().unwrap().add_dependency("Second", "Missing").unwrap();
+        assert!(matches!(
+            coordinator.start_all().await.unwrap_err(),
+            ThreadWorkerError::MissingDependency { dependency, .. } if dependency == "Missing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_start_all_stops_started_workers_when_one_never_gets_ready() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let event_list: EventList = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            save_recording_worker(&mut repository_guard, "Connection", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Receiver", Some(Duration::from_secs(60)), &event_list);
+            save_recording_worker(&mut repository_guard, "Dispatcher", None, &event_list);
+
+            repository_guard.add_dependency("Receiver", "Connection").unwrap();
+            repository_guard.add_dependency("Dispatcher", "Receiver").unwrap();
+        }
+
+        let coordinator = StartupCoordinator::new(Arc::clone(&repository))
+            .with_readiness_timeout(Duration::from_millis(20));
+
+        assert_eq!(coordinator.start_all().await.unwrap_err(), ThreadWorkerError::Timeout("Receiver".to_string()));
+
+        for name in ["Connection", "Receiver"] {
+            let worker_handle = repository.lock().unwrap().find_worker_handle(name).unwrap();
+            assert_eq!(worker_handle.join().await, WorkerExitStatus::Completed);
+        }
+
+        assert_eq!(event_list.lock().unwrap()[2..], ["stop Receiver", "stop Connection"]);
+        assert_eq!(repository.lock().unwrap().find_by_name("Dispatcher").unwrap().state(), ThreadWorkerState::Registered);
+    }
+
+    #[tokio::test]
+    async fn test_start_all_skips_workers_without_function() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let event_list: EventList = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let mut repository_guard = repository.lock().unwrap();
+            save_recording_worker(&mut repository_guard, "Connection", None, &event_list);
+            save_recording_worker(&mut repository_guard, "Dispatcher", None, &event_list);
+            repository_guard.save_thread_worker("Placeholder", None);
+            repository_guard.add_dependency("Dispatcher", "Placeholder").unwrap();
+        }
+
+        let startup_report = StartupCoordinator::new(Arc::clone(&repository)).start_all().await.unwrap();
+
+        let started_name_list: Vec<&str> = startup_report.get_worker_handle_list().iter().map(WorkerHandle::name).collect();
+        assert_eq!(started_name_list, vec!["Connection"]);
+        assert_eq!(startup_report.get_skipped_worker_list(), &vec!["Placeholder", "Dispatcher"]);
+        assert_eq!(repository.lock().unwrap().find_by_name("Dispatcher").unwrap().state(), ThreadWorkerState::Registered);
+
+        repository.lock().unwrap().stop_thread_worker("Connection").unwrap();
+    }
+}
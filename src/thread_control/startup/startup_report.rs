@@ -0,0 +1,34 @@
+use crate::thread_control::entity::worker_handle::WorkerHandle;
+
+#[derive(Debug, Default, Clone)]
+pub struct StartupReport {
+    worker_handle_list: Vec<WorkerHandle>,
+    skipped_worker_list: Vec<String>,
+}
+
+impl StartupReport {
+    pub fn new() -> Self {
+        StartupReport::default()
+    }
+
+    pub fn record_started(&mut self, worker_handle: WorkerHandle) {
+        self.worker_handle_list.push(worker_handle);
+    }
+
+    pub fn record_skipped(&mut self, name: &str) {
+        self.skipped_worker_list.push(name.to_string());
+    }
+
+    // In start order, so stopping them back to front stops dependents first
+    pub fn get_worker_handle_list(&self) -> &Vec<WorkerHandle> {
+        &self.worker_handle_list
+    }
+
+    pub fn get_skipped_worker_list(&self) -> &Vec<String> {
+        &self.skipped_worker_list
+    }
+
+    pub fn is_skipped(&self, name: &str) -> bool {
+        self.skipped_worker_list.iter().any(|skipped_name| skipped_name == name)
+    }
+}
@@ -0,0 +1 @@
+pub mod thread_worker_supervisor;
\ No newline at end of file
@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+use crate::thread_control::entity::restart_policy::RestartPolicy;
+use crate::thread_control::entity::supervision_policy::SupervisionPolicy;
+use crate::thread_control::entity::worker_exit_status::WorkerExitStatus;
+use crate::thread_control::error::thread_worker_error::ThreadWorkerError;
+use crate::thread_control::repository::thread_worker_repository::ThreadWorkerRepositoryTrait;
+use crate::thread_control::repository::thread_worker_repository_impl::ThreadWorkerRepositoryImpl;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupervisionOutcome {
+    Stopped,
+    Exited(WorkerExitStatus),
+    Escalated(WorkerExitStatus),
+    StartFailed(ThreadWorkerError),
+}
+
+pub struct ThreadWorkerSupervisor {
+    repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>,
+}
+
+impl ThreadWorkerSupervisor {
+    pub fn new(repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>) -> Self {
+        ThreadWorkerSupervisor { repository }
+    }
+
+    // Starts the worker and keeps restarting it according to its supervision policy.
+    // Workers without a policy are started once and left alone, as if registered with RestartPolicy::Never.
+    pub fn supervise(&self, name: &str) -> JoinHandle<SupervisionOutcome> {
+        let repository = Arc::clone(&self.repository);
+        let name = name.to_string();
+
+        tokio::spawn(async move {
+            ThreadWorkerSupervisor::run_supervision(repository, &name).await
+        })
+    }
+
+    async fn run_supervision(repository: Arc<Mutex<ThreadWorkerRepositoryImpl>>, name: &str) -> SupervisionOutcome {
+        let (supervision_policy, shutdown_token) = match repository.lock() {
+            Ok(repository) => {
+                let supervision_policy = repository
+                    .find_by_name(name)
+                    .and_then(|worker| worker.get_supervision_policy().cloned())
+                    .unwrap_or_else(|| SupervisionPolicy::new(RestartPolicy::Never));
+
+                (supervision_policy, repository.get_shutdown_token())
+            }
+            Err(error) => return SupervisionOutcome::StartFailed(error.into()),
+        };
+
+        let mut restart_history: VecDeque<Instant> = VecDeque::new();
+
+        loop {
+            let start_result = match repository.lock() {
+                Ok(mut repository) => repository.start_thread_worker(name),
+                Err(error) => Err(error.into()),
+            };
+
+            let worker_handle = match start_result {
+                Ok(worker_handle) => worker_handle,
+                Err(error) => return SupervisionOutcome::StartFailed(error),
+            };
+
+            let exit_status = worker_handle.join().await;
+
+            // A cancelled token means someone stopped the worker on purpose, not that it crashed
+            if worker_handle.cancellation_token().is_cancelled() || exit_status == WorkerExitStatus::Cancelled {
+                return SupervisionOutcome::Stopped;
+            }
+
+            if !supervision_policy.restart_policy().should_restart(&exit_status) {
+                return SupervisionOutcome::Exited(exit_status);
+            }
+
+            let now = Instant::now();
+            while let Some(restarted_at) = restart_history.front() {
+                if now.duration_since(*restarted_at) <= supervision_policy.restart_window() {
+                    break;
+                }
+                restart_history.pop_front();
+            }
+
+            if restart_history.len() >= supervision_policy.max_restarts() {
+                supervision_policy.escalate(name, &exit_status);
+                return SupervisionOutcome::Escalated(exit_status);
+            }
+
+            let backoff = supervision_policy.backoff_for(restart_history.len());
+            restart_history.push_back(now);
+
+            // Stopping the worker while it waits to be restarted cancels the finished run's token
+            tokio::select! {
+                _ = sleep(backoff) => {}
+                _ = shutdown_token.cancelled() => return SupervisionOutcome::Stopped,
+                _ = worker_handle.cancellation_token().cancelled() => return SupervisionOutcome::Stopped,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use crate::thread_control::entity::thread_worker_context::ThreadWorkerContext;
+    use crate::thread_control::entity::thread_worker_state::ThreadWorkerState;
+
+    fn counting_panic_function(run_count: Arc<AtomicUsize>) -> impl Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + 'static {
+        move || -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let run_count = Arc::clone(&run_count);
+            Box::pin(async move {
+                run_count.fetch_add(1, Ordering::SeqCst);
+                panic!("Supervised worker crashed");
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_on_failure_until_escalation() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let run_count = Arc::new(AtomicUsize::new(0));
+        let escalated_worker = Arc::new(Mutex::new(None));
+        let hook_escalated_worker = Arc::clone(&escalated_worker);
+
+        let supervision_policy = SupervisionPolicy::new(RestartPolicy::OnFailure)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5), 2)
+            .with_max_restarts(2, Duration::from_secs(60))
+            .with_escalation_hook(Arc::new(move |name: &str, _: &WorkerExitStatus| {
+                *hook_escalated_worker.lock().unwrap() = Some(name.to_string());
+            }));
+
+        {
+            let mut repository = repository.lock().unwrap();
+            repository.save_thread_worker("CrashingWorker", Some(Box::new(counting_panic_function(Arc::clone(&run_count)))));
+            repository.set_supervision_policy("CrashingWorker", supervision_policy).unwrap();
+        }
+
+        let outcome = ThreadWorkerSupervisor::new(repository).supervise("CrashingWorker").await.unwrap();
+
+        assert_eq!(
+            outcome,
+            SupervisionOutcome::Escalated(WorkerExitStatus::Panicked("Supervised worker crashed".to_string()))
+        );
+        assert_eq!(run_count.load(Ordering::SeqCst), 3);
+        assert_eq!(escalated_worker.lock().unwrap().as_deref(), Some("CrashingWorker"));
+    }
+
+    #[tokio::test]
+    async fn test_worker_without_policy_runs_once() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let run_count = Arc::new(AtomicUsize::new(0));
+
+        repository.lock().unwrap()
+            .save_thread_worker("UnsupervisedWorker", Some(Box::new(counting_panic_function(Arc::clone(&run_count)))));
+
+        let outcome = ThreadWorkerSupervisor::new(repository).supervise("UnsupervisedWorker").await.unwrap();
+
+        assert!(matches!(outcome, SupervisionOutcome::Exited(WorkerExitStatus::Panicked(_))));
+        assert_eq!(run_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_always_restart_stops_on_shutdown() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let run_count = Arc::new(AtomicUsize::new(0));
+        let worker_run_count = Arc::clone(&run_count);
+
+        let heartbeat_function = move |context: ThreadWorkerContext| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+            let run_count = Arc::clone(&worker_run_count);
+            Box::pin(async move {
+                run_count.fetch_add(1, Ordering::SeqCst);
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(5)) => {}
+                    _ = context.cancelled() => {}
+                }
+            })
+        };
+
+        let shutdown_token = {
+            let mut repository = repository.lock().unwrap();
+            repository.save_thread_worker_with_context("HeartbeatWorker", Box::new(heartbeat_function));
+            repository.set_supervision_policy(
+                "HeartbeatWorker",
+                SupervisionPolicy::new(RestartPolicy::Always)
+                    .with_backoff(Duration::from_millis(1), Duration::from_millis(1), 1)
+                    .with_max_restarts(1000, Duration::from_secs(60)),
+            ).unwrap();
+            repository.get_shutdown_token()
+        };
+
+        let supervision = ThreadWorkerSupervisor::new(repository).supervise("HeartbeatWorker");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while run_count.load(Ordering::SeqCst) < 3 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }).await.expect("the supervisor never restarted the worker twice");
+        shutdown_token.cancel();
+
+        assert_eq!(supervision.await.unwrap(), SupervisionOutcome::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_stop_during_backoff_prevents_restart() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+        let run_count = Arc::new(AtomicUsize::new(0));
+
+        {
+            let mut repository = repository.lock().unwrap();
+            repository.save_thread_worker("BackingOffWorker", Some(Box::new(counting_panic_function(Arc::clone(&run_count)))));
+            repository.set_supervision_policy(
+                "BackingOffWorker",
+                SupervisionPolicy::new(RestartPolicy::OnFailure)
+                    .with_backoff(Duration::from_secs(60), Duration::from_secs(60), 1),
+            ).unwrap();
+        }
+
+        let supervision = ThreadWorkerSupervisor::new(Arc::clone(&repository)).supervise("BackingOffWorker");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while repository.lock().unwrap().find_by_name("BackingOffWorker").unwrap().state() != ThreadWorkerState::Failed {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }).await.expect("the supervised worker never failed");
+        repository.lock().unwrap().stop_thread_worker("BackingOffWorker").unwrap();
+
+        let outcome = tokio::time::timeout(Duration::from_secs(5), supervision).await.expect("the supervisor kept waiting to restart");
+        assert_eq!(outcome.unwrap(), SupervisionOutcome::Stopped);
+        assert_eq!(run_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_supervise_missing_worker() {
+        let repository = Arc::new(Mutex::new(ThreadWorkerRepositoryImpl::new()));
+
+        let outcome = ThreadWorkerSupervisor::new(repository).supervise("MissingWorker").await.unwrap();
+
+        assert_eq!(outcome, SupervisionOutcome::StartFailed(ThreadWorkerError::NotFound("MissingWorker".to_string())));
+    }
+}